@@ -0,0 +1,380 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    io::{self, BufWriter, Write},
+    ops::Range,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use pyo3::{call::PyCallArgs, exceptions::PyFileNotFoundError, prelude::*};
+use tantivy::{
+    directory::{
+        error::{DeleteError, OpenReadError, OpenWriteError},
+        AntiCallToken, Directory, FileHandle, OwnedBytes, TerminatingWrite,
+        WatchCallback, WatchCallbackList, WatchHandle, WritePtr,
+    },
+    HasLen,
+};
+
+use crate::index::META_FILEPATH;
+
+/// A `tantivy::Directory` storing the files of an index through a Python
+/// object, which must have the following methods:
+///
+/// - `read_range(path, start, end) -> bytes`
+/// - `file_size(path) -> int`
+/// - `write(path, data)`, called with the whole content of new files
+/// - `atomic_write(path, data)`, replacing the file atomically
+/// - `delete(path)`
+///
+/// `read_range`, `file_size` and `delete` raise `FileNotFoundError` for
+/// missing files.
+#[derive(Clone)]
+pub(crate) struct PyDirectory {
+    storage: Arc<Py<PyAny>>,
+    cache: Option<Arc<ReadCache>>,
+    watch_router: Arc<WatchCallbackList>,
+}
+
+impl PyDirectory {
+    /// Wraps `storage`, caching up to `read_cache_size` bytes of the
+    /// segment files that are read.
+    pub(crate) fn new(storage: Py<PyAny>, read_cache_size: usize) -> Self {
+        PyDirectory {
+            storage: Arc::new(storage),
+            cache: (read_cache_size > 0)
+                .then(|| Arc::new(ReadCache::new(read_cache_size))),
+            watch_router: Arc::default(),
+        }
+    }
+
+    fn call<T>(
+        &self,
+        method: &str,
+        args: impl for<'py> PyCallArgs<'py>,
+        extract: impl for<'py> FnOnce(Bound<'py, PyAny>) -> PyResult<T>,
+    ) -> io::Result<T> {
+        Python::try_attach(|py| {
+            self.storage
+                .bind(py)
+                .call_method1(method, args)
+                .and_then(extract)
+                .map_err(|err| {
+                    let kind = if err.is_instance_of::<PyFileNotFoundError>(py)
+                    {
+                        io::ErrorKind::NotFound
+                    } else {
+                        io::ErrorKind::Other
+                    };
+                    io::Error::new(kind, format!("{method} failed: {err}"))
+                })
+        })
+        .unwrap_or_else(|| {
+            Err(io::Error::other(format!(
+                "{method} failed: the Python interpreter is shutting down"
+            )))
+        })
+    }
+
+    fn read_range(
+        &self,
+        path: &Path,
+        range: Range<usize>,
+    ) -> io::Result<Vec<u8>> {
+        let bytes: Vec<u8> = self.call(
+            "read_range",
+            (path.to_string_lossy(), range.start, range.end),
+            |bytes| bytes.extract(),
+        )?;
+        if bytes.len() != range.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                format!(
+                    "read_range returned {} bytes instead of {} for `{}`.",
+                    bytes.len(),
+                    range.len(),
+                    path.display()
+                ),
+            ));
+        }
+        Ok(bytes)
+    }
+
+    fn file_size(&self, path: &Path) -> Result<usize, OpenReadError> {
+        self.call("file_size", (path.to_string_lossy(),), |size| {
+            size.extract()
+        })
+        .map_err(|err| open_read_error(path, err))
+    }
+
+    fn write_file(
+        &self,
+        path: &Path,
+        data: &[u8],
+        method: &str,
+    ) -> io::Result<()> {
+        if let Some(cache) = &self.cache {
+            cache.remove_file(path);
+        }
+        self.call(method, (path.to_string_lossy(), data), |_| Ok(()))
+    }
+}
+
+impl fmt::Debug for PyDirectory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PyDirectory")
+    }
+}
+
+fn open_read_error(path: &Path, err: io::Error) -> OpenReadError {
+    if err.kind() == io::ErrorKind::NotFound {
+        OpenReadError::FileDoesNotExist(path.to_path_buf())
+    } else {
+        OpenReadError::wrap_io_error(err, path.to_path_buf())
+    }
+}
+
+impl Directory for PyDirectory {
+    fn get_file_handle(
+        &self,
+        path: &Path,
+    ) -> Result<Arc<dyn FileHandle>, OpenReadError> {
+        let len = self.file_size(path)?;
+        Ok(Arc::new(PyFileHandle {
+            directory: self.clone(),
+            path: path.to_path_buf(),
+            len,
+        }))
+    }
+
+    fn delete(&self, path: &Path) -> Result<(), DeleteError> {
+        if let Some(cache) = &self.cache {
+            cache.remove_file(path);
+        }
+        self.call("delete", (path.to_string_lossy(),), |_| Ok(()))
+            .map_err(|err| {
+                if err.kind() == io::ErrorKind::NotFound {
+                    DeleteError::FileDoesNotExist(path.to_path_buf())
+                } else {
+                    DeleteError::IoError {
+                        io_error: Arc::new(err),
+                        filepath: path.to_path_buf(),
+                    }
+                }
+            })
+    }
+
+    fn exists(&self, path: &Path) -> Result<bool, OpenReadError> {
+        match self.file_size(path) {
+            Ok(_) => Ok(true),
+            Err(OpenReadError::FileDoesNotExist(_)) => Ok(false),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn open_write(&self, path: &Path) -> Result<WritePtr, OpenWriteError> {
+        if self.exists(path).map_err(|err| {
+            OpenWriteError::wrap_io_error(
+                io::Error::other(err.to_string()),
+                path.to_path_buf(),
+            )
+        })? {
+            return Err(OpenWriteError::FileAlreadyExists(path.to_path_buf()));
+        }
+        Ok(BufWriter::new(Box::new(PyFileWriter {
+            directory: self.clone(),
+            path: path.to_path_buf(),
+            data: Vec::new(),
+            created: false,
+        })))
+    }
+
+    fn atomic_read(&self, path: &Path) -> Result<Vec<u8>, OpenReadError> {
+        let len = self.file_size(path)?;
+        self.read_range(path, 0..len)
+            .map_err(|err| open_read_error(path, err))
+    }
+
+    fn atomic_write(&self, path: &Path, data: &[u8]) -> io::Result<()> {
+        self.write_file(path, data, "atomic_write")?;
+        if path == Path::new(META_FILEPATH) {
+            drop(self.watch_router.broadcast());
+        }
+        Ok(())
+    }
+
+    fn sync_directory(&self) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn watch(
+        &self,
+        watch_callback: WatchCallback,
+    ) -> tantivy::Result<WatchHandle> {
+        Ok(self.watch_router.subscribe(watch_callback))
+    }
+}
+
+/// A file of a `PyDirectory`, read on demand.
+#[derive(Debug)]
+struct PyFileHandle {
+    directory: PyDirectory,
+    path: PathBuf,
+    len: usize,
+}
+
+impl HasLen for PyFileHandle {
+    fn len(&self) -> usize {
+        self.len
+    }
+}
+
+impl FileHandle for PyFileHandle {
+    fn read_bytes(&self, range: Range<usize>) -> io::Result<OwnedBytes> {
+        let Some(cache) = &self.directory.cache else {
+            return Ok(OwnedBytes::new(
+                self.directory.read_range(&self.path, range)?,
+            ));
+        };
+        if let Some(bytes) = cache.get(&self.path, &range) {
+            return Ok(bytes);
+        }
+        let bytes = OwnedBytes::new(
+            self.directory.read_range(&self.path, range.clone())?,
+        );
+        cache.insert(&self.path, range, bytes.clone());
+        Ok(bytes)
+    }
+}
+
+/// A new file of a `PyDirectory`, written when it is complete.
+struct PyFileWriter {
+    directory: PyDirectory,
+    path: PathBuf,
+    data: Vec<u8>,
+    /// Whether the file was created empty by `flush()`.
+    created: bool,
+}
+
+impl Write for PyFileWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.data.extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    /// Creates the file if nothing was written to it yet, as tantivy creates
+    /// its lock files by flushing them without ever terminating them.
+    fn flush(&mut self) -> io::Result<()> {
+        if self.data.is_empty() && !self.created {
+            self.directory.write_file(&self.path, &[], "write")?;
+            self.created = true;
+        }
+        Ok(())
+    }
+}
+
+impl TerminatingWrite for PyFileWriter {
+    fn terminate_ref(&mut self, _: AntiCallToken) -> io::Result<()> {
+        self.directory.write_file(&self.path, &self.data, "write")
+    }
+}
+
+/// Least recently used byte ranges read from the files of a `PyDirectory`.
+/// A read is served from a cached range of the file that contains it.
+///
+/// Only the segment files are read through file handles, which tantivy never
+/// modifies once written.
+struct ReadCache {
+    capacity: usize,
+    state: Mutex<ReadCacheState>,
+}
+
+#[derive(Default)]
+struct ReadCacheState {
+    size: usize,
+    /// Incremented on every access, to order the entries by last use.
+    clock: u64,
+    /// The cached ranges of every file, by start offset.
+    files: HashMap<PathBuf, BTreeMap<usize, CachedRange>>,
+    by_last_use: BTreeMap<u64, (PathBuf, usize)>,
+}
+
+struct CachedRange {
+    bytes: OwnedBytes,
+    last_use: u64,
+}
+
+impl ReadCache {
+    fn new(capacity: usize) -> Self {
+        ReadCache {
+            capacity,
+            state: Mutex::default(),
+        }
+    }
+
+    fn get(&self, path: &Path, range: &Range<usize>) -> Option<OwnedBytes> {
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        let (&start, cached) = state
+            .files
+            .get_mut(path)?
+            .range_mut(..=range.start)
+            .next_back()?;
+        if start + cached.bytes.len() < range.end {
+            return None;
+        }
+        state.clock += 1;
+        let key = state.by_last_use.remove(&cached.last_use)?;
+        cached.last_use = state.clock;
+        state.by_last_use.insert(state.clock, key);
+        Some(cached.bytes.slice(range.start - start..range.end - start))
+    }
+
+    fn insert(&self, path: &Path, range: Range<usize>, bytes: OwnedBytes) {
+        if bytes.len() > self.capacity {
+            return;
+        }
+        let mut state = self.state.lock().unwrap();
+        let state = &mut *state;
+        state.clock += 1;
+        state.size += bytes.len();
+        let cached = CachedRange {
+            bytes,
+            last_use: state.clock,
+        };
+        let file = state.files.entry(path.to_path_buf()).or_default();
+        if let Some(previous) = file.insert(range.start, cached) {
+            state.size -= previous.bytes.len();
+            state.by_last_use.remove(&previous.last_use);
+        }
+        state
+            .by_last_use
+            .insert(state.clock, (path.to_path_buf(), range.start));
+        while state.size > self.capacity {
+            let Some((_, (path, start))) = state.by_last_use.pop_first() else {
+                break;
+            };
+            let Some(file) = state.files.get_mut(&path) else {
+                continue;
+            };
+            if let Some(cached) = file.remove(&start) {
+                state.size -= cached.bytes.len();
+            }
+            if file.is_empty() {
+                state.files.remove(&path);
+            }
+        }
+    }
+
+    fn remove_file(&self, path: &Path) {
+        let mut state = self.state.lock().unwrap();
+        let Some(file) = state.files.remove(path) else {
+            return;
+        };
+        for cached in file.into_values() {
+            state.size -= cached.bytes.len();
+            state.by_last_use.remove(&cached.last_use);
+        }
+    }
+}
@@ -5,14 +5,17 @@ use itertools::Itertools;
 use pyo3::{
     basic::CompareOp,
     prelude::*,
+    sync::PyOnceLock,
     types::{
-        PyAny, PyBool, PyDateTime, PyDict, PyInt, PyList, PyTuple, PyTzInfo,
-        PyTzInfoAccess,
+        PyAny, PyBool, PyDate, PyDateTime, PyDict, PyFloat, PyInt, PyList,
+        PyString, PyTuple, PyType, PyTzInfo, PyTzInfoAccess,
     },
     IntoPyObjectExt, Python,
 };
 
-use chrono::{DateTime as ChronoDateTime, NaiveDateTime, Utc};
+use chrono::{
+    DateTime as ChronoDateTime, NaiveDate, NaiveDateTime, NaiveTime, Utc,
+};
 
 use tantivy::{self as tv, schema::document::OwnedValue as Value};
 
@@ -39,8 +42,18 @@ use std::{
 /// `DateTime<Utc>` only accepts inputs whose tzinfo *is* `datetime.timezone.utc`
 /// — a `zoneinfo.ZoneInfo("UTC")` or any non-UTC tz fails. Normalizing in
 /// Python first sidesteps both restrictions.
+///
+/// A `date` is converted to the midnight UTC of that day.
 fn pydatetime_to_tv(any: &Bound<PyAny>) -> PyResult<tv::DateTime> {
-    let dt = any.downcast::<PyDateTime>()?;
+    let Ok(dt) = any.downcast::<PyDateTime>() else {
+        let nanos = any
+            .extract::<NaiveDate>()?
+            .and_time(NaiveTime::MIN)
+            .and_utc()
+            .timestamp_nanos_opt()
+            .ok_or_else(|| to_pyerr("date out of representable range"))?;
+        return Ok(tv::DateTime::from_timestamp_nanos(nanos));
+    };
     let nanos = if dt.get_tzinfo().is_some() {
         let utc_tz = PyTzInfo::utc(dt.py())?;
         let utc_dt = dt.call_method1("astimezone", (utc_tz,))?;
@@ -62,7 +75,93 @@ fn tv_to_pydatetime(py: Python, dt: tv::DateTime) -> PyResult<Py<PyAny>> {
         .into_py_any(py)
 }
 
+static DECIMAL_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static UUID_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static IPV4_ADDRESS_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static IPV6_ADDRESS_TYPE: PyOnceLock<Py<PyType>> = PyOnceLock::new();
+static IP_ADDRESS: PyOnceLock<Py<PyAny>> = PyOnceLock::new();
+
+fn is_uuid(any: &Bound<PyAny>) -> PyResult<bool> {
+    any.is_instance(UUID_TYPE.import(any.py(), "uuid", "UUID")?)
+}
+
+fn is_ip_address(any: &Bound<PyAny>) -> PyResult<bool> {
+    let py = any.py();
+    Ok(any.is_instance(IPV4_ADDRESS_TYPE.import(
+        py,
+        "ipaddress",
+        "IPv4Address",
+    )?)? || any.is_instance(IPV6_ADDRESS_TYPE.import(
+        py,
+        "ipaddress",
+        "IPv6Address",
+    )?)?)
+}
+
+/// Convert the Python values that have a builtin counterpart to it: numpy
+/// scalars to the matching Python scalar, and `Decimal` to an `int` if it
+/// is integral or to a `float` otherwise.
+fn to_builtin_value<'py>(
+    any: &Bound<'py, PyAny>,
+) -> PyResult<Bound<'py, PyAny>> {
+    if any.is_exact_instance_of::<PyString>()
+        || any.is_exact_instance_of::<PyBool>()
+        || any.is_exact_instance_of::<PyInt>()
+        || any.is_exact_instance_of::<PyFloat>()
+    {
+        return Ok(any.clone());
+    }
+    // numpy is an optional dependency, so its scalars are recognized by the
+    // module of their type rather than by importing it.
+    if any.get_type().module()?.to_str()? == "numpy" && any.hasattr("item")? {
+        return any.call_method0("item");
+    }
+    if any.is_instance(DECIMAL_TYPE.import(
+        any.py(),
+        "decimal",
+        "Decimal",
+    )?)? {
+        let is_integral = any
+            .call_method0("as_integer_ratio")
+            .and_then(|ratio| ratio.get_item(1)?.eq(1))
+            .unwrap_or(false);
+        return if is_integral {
+            any.call_method0("__int__")
+        } else {
+            any.call_method0("__float__")
+        };
+    }
+    Ok(any.clone())
+}
+
+/// Convert a Python IP address, either a string or an `ipaddress` object, to
+/// an IPv6 address. IPv4 addresses are mapped to IPv6 as tantivy expects.
+fn py_to_ip_addr(any: &Bound<PyAny>) -> PyResult<Ipv6Addr> {
+    let text = if is_ip_address(any)? {
+        any.str()?.to_string()
+    } else {
+        any.extract::<String>()?
+    };
+    let addr = IpAddr::from_str(&text).map_err(to_pyerr)?;
+    Ok(match addr {
+        IpAddr::V4(addr) => addr.to_ipv6_mapped(),
+        IpAddr::V6(addr) => addr,
+    })
+}
+
+/// Convert a tantivy IP address to an `ipaddress` object. IPv4-mapped
+/// addresses are returned as `IPv4Address`.
+fn ip_addr_to_py(py: Python, addr: &Ipv6Addr) -> PyResult<Py<PyAny>> {
+    let text = match addr.to_ipv4_mapped() {
+        Some(addr) => addr.to_string(),
+        None => addr.to_string(),
+    };
+    let ip_address = IP_ADDRESS.import(py, "ipaddress", "ip_address")?;
+    Ok(ip_address.call1((text,))?.unbind())
+}
+
 pub(crate) fn extract_value(any: &Bound<PyAny>) -> PyResult<Value> {
+    let any = &to_builtin_value(any)?;
     if let Ok(s) = any.extract::<String>() {
         return Ok(Value::Str(s));
     }
@@ -75,7 +174,13 @@ pub(crate) fn extract_value(any: &Bound<PyAny>) -> PyResult<Value> {
     if let Ok(num) = any.extract::<f64>() {
         return Ok(Value::F64(num));
     }
-    if any.downcast::<PyDateTime>().is_ok() {
+    if is_uuid(any)? {
+        return Ok(Value::Str(any.str()?.to_string()));
+    }
+    if is_ip_address(any)? {
+        return Ok(Value::IpAddr(py_to_ip_addr(any)?));
+    }
+    if any.downcast::<PyDate>().is_ok() {
         return Ok(Value::Date(pydatetime_to_tv(any)?));
     }
     if let Ok(facet) = any.extract::<Facet>() {
@@ -117,7 +222,11 @@ pub(crate) fn extract_value_for_type(
         }
     }
 
+    let any = &to_builtin_value(any)?;
     let value = match tv_type {
+        tv::schema::Type::Str if is_uuid(any)? => {
+            Value::Str(any.str()?.to_string())
+        }
         tv::schema::Type::Str => Value::Str(
             any.extract::<String>()
                 .map_err(to_pyerr_for_type("Str", field_name, any))?,
@@ -147,6 +256,9 @@ pub(crate) fn extract_value_for_type(
                 .map_err(to_pyerr_for_type("Facet", field_name, any))?
                 .inner,
         ),
+        tv::schema::Type::Bytes if is_uuid(any)? => {
+            Value::Bytes(any.getattr("bytes")?.extract::<Vec<u8>>()?)
+        }
         tv::schema::Type::Bytes => Value::Bytes(
             any.extract::<Vec<u8>>()
                 .map_err(to_pyerr_for_type("Bytes", field_name, any))?,
@@ -168,24 +280,86 @@ pub(crate) fn extract_value_for_type(
             )?;
             Value::Object(map.into_iter().collect())
         }
-        tv::schema::Type::IpAddr => {
-            let val = any
-                .extract::<&str>()
-                .map_err(to_pyerr_for_type("IpAddr", field_name, any))?;
-
-            IpAddr::from_str(val)
-                .map(|addr| match addr {
-                    IpAddr::V4(addr) => addr.to_ipv6_mapped(),
-                    IpAddr::V6(addr) => addr,
-                })
-                .map(Value::IpAddr)
-                .map_err(to_pyerr_for_type("IpAddr", field_name, any))?
-        }
+        tv::schema::Type::IpAddr => Value::IpAddr(
+            py_to_ip_addr(any)
+                .map_err(to_pyerr_for_type("IpAddr", field_name, any))?,
+        ),
     };
 
     Ok(value)
 }
 
+/// Returns true if the Python type of `any` is one that can be stored in a
+/// field of the given type, regardless of its actual value.
+fn is_accepted_py_type(
+    any: &Bound<PyAny>,
+    tv_type: tv::schema::Type,
+) -> PyResult<bool> {
+    let any = &to_builtin_value(any)?;
+    let is_number = (any.is_instance_of::<PyInt>()
+        || any.is_instance_of::<PyFloat>())
+        && !any.is_instance_of::<PyBool>();
+    Ok(match tv_type {
+        tv::schema::Type::Str => {
+            any.is_instance_of::<PyString>() || is_uuid(any)?
+        }
+        tv::schema::Type::U64
+        | tv::schema::Type::I64
+        | tv::schema::Type::F64 => is_number,
+        tv::schema::Type::Bool => any.is_instance_of::<PyBool>(),
+        tv::schema::Type::Date => any.is_instance_of::<PyDate>(),
+        tv::schema::Type::Facet => any.extract::<Facet>().is_ok(),
+        tv::schema::Type::Bytes => {
+            any.extract::<Vec<u8>>().is_ok() || is_uuid(any)?
+        }
+        tv::schema::Type::Json => {
+            any.is_instance_of::<PyString>() || any.is_instance_of::<PyDict>()
+        }
+        tv::schema::Type::IpAddr => {
+            any.is_instance_of::<PyString>() || is_ip_address(any)?
+        }
+    })
+}
+
+/// Checks that a Python value, or list of values, can be stored in a field.
+///
+/// Returns the number of values along with a `(kind, message)` pair for
+/// every value that cannot be stored, where `kind` is "wrong_type" if the
+/// Python type of the value is not accepted by the field and
+/// "invalid_value" if the type is accepted but the value itself is not,
+/// e.g. a malformed IP address or a negative unsigned integer.
+pub(crate) fn check_py_values_for_type(
+    any: &Bound<PyAny>,
+    field_type: &tv::schema::FieldType,
+    field_name: &str,
+) -> PyResult<(usize, Vec<(&'static str, String)>)> {
+    let tv_type = field_type.value_type();
+    let values = match any.downcast::<PyList>() {
+        // A list of integers is a single value for a bytes field.
+        Ok(values)
+            if !(tv_type == tv::schema::Type::Bytes
+                && values
+                    .get_item(0)
+                    .is_ok_and(|v| v.is_instance_of::<PyInt>())) =>
+        {
+            values.iter().collect()
+        }
+        _ => vec![any.clone()],
+    };
+    let mut problems = Vec::new();
+    for value in values.iter() {
+        if let Err(err) = extract_value_for_type(value, tv_type, field_name) {
+            let kind = if is_accepted_py_type(value, tv_type)? {
+                "invalid_value"
+            } else {
+                "wrong_type"
+            };
+            problems.push((kind, err.value(any.py()).to_string()));
+        }
+    }
+    Ok((values.len(), problems))
+}
+
 fn extract_value_single_or_list(any: &Bound<PyAny>) -> PyResult<Vec<Value>> {
     if let Ok(values) = any.downcast::<PyList>() {
         values.iter().map(|v| extract_value(&v)).collect()
@@ -242,7 +416,7 @@ fn object_to_py(py: Python, obj: &[(String, Value)]) -> PyResult<Py<PyAny>> {
     Ok(dict.into())
 }
 
-fn value_to_py(py: Python, value: &Value) -> PyResult<Py<PyAny>> {
+pub(crate) fn value_to_py(py: Python, value: &Value) -> PyResult<Py<PyAny>> {
     Ok(match value {
         Value::Null => py.None(),
         Value::Str(text) => text.into_py_any(py)?,
@@ -271,7 +445,7 @@ fn value_to_py(py: Python, value: &Value) -> PyResult<Py<PyAny>> {
         }
         Value::Object(obj) => object_to_py(py, obj)?,
         Value::Bool(b) => b.into_py_any(py)?,
-        Value::IpAddr(i) => (*i).to_string().into_py_any(py)?,
+        Value::IpAddr(i) => ip_addr_to_py(py, i)?,
     })
 }
 
@@ -385,8 +559,8 @@ fn deserialize_json_object_as_i64_inner(
 ///
 /// [`BorrowedSerdeValue`] is often used for the serialization path, as owning the data is not
 /// necessary for serialization.
-#[derive(Deserialize, Serialize)]
-enum SerdeValue {
+#[derive(Clone, Deserialize, Serialize)]
+pub(crate) enum SerdeValue {
     /// Null
     Null,
     /// The str type is used for any text information.
@@ -736,11 +910,12 @@ impl Document {
     ///
     /// Args:
     ///     field_name (str): The field name for which we are adding the date.
-    ///     value (datetime): The date that will be added to the document.
+    ///     value (datetime | date): The date that will be added to the
+    ///         document. A `date` is added as the midnight UTC of that day.
     fn add_date(
         &mut self,
         field_name: String,
-        value: &Bound<PyDateTime>,
+        value: &Bound<PyDate>,
     ) -> PyResult<()> {
         let dt = pydatetime_to_tv(value.as_any())?;
         self.add_value(field_name, dt);
@@ -796,18 +971,17 @@ impl Document {
     ///
     /// Args:
     ///     field_name (str): The field for which we are adding the IP address.
-    ///     value (str): The IP address object that will be added
-    ///         to the document.
+    ///     value (str | IPv4Address | IPv6Address): The IP address that will
+    ///         be added to the document.
     ///
     /// Raises a ValueError if the IP address is invalid.
-    fn add_ip_addr(&mut self, field_name: String, value: &str) -> PyResult<()> {
-        let ip_addr = IpAddr::from_str(value).map_err(to_pyerr)?;
-        match ip_addr {
-            IpAddr::V4(addr) => {
-                self.add_value(field_name, addr.to_ipv6_mapped())
-            }
-            IpAddr::V6(addr) => self.add_value(field_name, addr),
-        }
+    fn add_ip_addr(
+        &mut self,
+        field_name: String,
+        value: &Bound<PyAny>,
+    ) -> PyResult<()> {
+        let ip_addr = py_to_ip_addr(value)?;
+        self.add_value(field_name, ip_addr);
         Ok(())
     }
 
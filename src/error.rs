@@ -0,0 +1,208 @@
+use std::{any::Any, io, path::Path};
+
+use pyo3::{exceptions::PyValueError, prelude::*, types::PyTuple};
+use tantivy::{
+    self as tv,
+    directory::error::{
+        LockError, OpenDirectoryError, OpenReadError, OpenWriteError,
+    },
+};
+
+/// Base class of the errors raised by tantivy.
+///
+/// It derives from `ValueError`, which used to be raised for all of them.
+#[pyclass(extends = PyValueError, subclass, frozen, module = "tantivy.tantivy")]
+pub(crate) struct TantivyError;
+
+#[pymethods]
+impl TantivyError {
+    #[new]
+    #[pyo3(signature = (*_args))]
+    fn new(_args: &Bound<PyTuple>) -> Self {
+        TantivyError
+    }
+}
+
+/// Raised when the index lock is held by another writer, possibly from a
+/// different process. The operation can be retried once it is released.
+#[pyclass(extends = TantivyError, frozen, module = "tantivy.tantivy")]
+pub(crate) struct LockBusyError;
+
+#[pymethods]
+impl LockBusyError {
+    #[new]
+    #[pyo3(signature = (*_args))]
+    fn new(_args: &Bound<PyTuple>) -> (Self, TantivyError) {
+        (LockBusyError, TantivyError)
+    }
+}
+
+/// Raised when the files of the index are missing or corrupted, the index
+/// must be rebuilt.
+#[pyclass(
+    extends = TantivyError,
+    subclass,
+    frozen,
+    module = "tantivy.tantivy"
+)]
+pub(crate) struct IndexCorruptedError;
+
+#[pymethods]
+impl IndexCorruptedError {
+    #[new]
+    #[pyo3(signature = (*_args))]
+    fn new(_args: &Bound<PyTuple>) -> (Self, TantivyError) {
+        (IndexCorruptedError, TantivyError)
+    }
+}
+
+/// Raised when the content of an index file is corrupted, for instance when
+/// its checksum does not match.
+#[pyclass(extends = IndexCorruptedError, frozen, module = "tantivy.tantivy")]
+pub(crate) struct DataCorruption;
+
+#[pymethods]
+impl DataCorruption {
+    #[new]
+    #[pyo3(signature = (*_args))]
+    fn new(_args: &Bound<PyTuple>) -> PyClassInitializer<Self> {
+        PyClassInitializer::from(TantivyError)
+            .add_subclass(IndexCorruptedError)
+            .add_subclass(DataCorruption)
+    }
+}
+
+/// Raised when the index was written by an incompatible version of tantivy,
+/// or with a compression it was not built with.
+#[pyclass(extends = TantivyError, frozen, module = "tantivy.tantivy")]
+pub(crate) struct IncompatibleIndexError;
+
+#[pymethods]
+impl IncompatibleIndexError {
+    #[new]
+    #[pyo3(signature = (*_args))]
+    fn new(_args: &Bound<PyTuple>) -> (Self, TantivyError) {
+        (IncompatibleIndexError, TantivyError)
+    }
+}
+
+/// Raised when a field is missing from the schema or the schema is invalid.
+#[pyclass(extends = TantivyError, frozen, module = "tantivy.tantivy")]
+pub(crate) struct SchemaError;
+
+#[pymethods]
+impl SchemaError {
+    #[new]
+    #[pyo3(signature = (*_args))]
+    fn new(_args: &Bound<PyTuple>) -> (Self, TantivyError) {
+        (SchemaError, TantivyError)
+    }
+}
+
+/// Raised when reading or writing the files of the index failed.
+#[pyclass(extends = TantivyError, frozen, module = "tantivy.tantivy")]
+pub(crate) struct IoError {
+    message: String,
+    /// The error number of the operating system, if any.
+    #[pyo3(get)]
+    errno: Option<i32>,
+}
+
+#[pymethods]
+impl IoError {
+    #[new]
+    #[pyo3(signature = (message, errno = None))]
+    fn new(message: String, errno: Option<i32>) -> (Self, TantivyError) {
+        (IoError { message, errno }, TantivyError)
+    }
+
+    fn __str__(&self) -> &str {
+        &self.message
+    }
+}
+
+fn io_error(message: String, err: Option<&io::Error>) -> PyErr {
+    PyErr::new::<IoError, _>((message, err.and_then(io::Error::raw_os_error)))
+}
+
+/// Builds the exception for an error, picking the class from the tantivy
+/// error it is, if any.
+pub(crate) fn to_pyerr(err: &dyn Any, message: String) -> PyErr {
+    if let Some(err) = err.downcast_ref::<tv::TantivyError>() {
+        tantivy_error(err, message)
+    } else if let Some(err) = err.downcast_ref::<OpenDirectoryError>() {
+        open_directory_error(err, message)
+    } else if let Some(err) = err.downcast_ref::<OpenReadError>() {
+        open_read_error(err, message)
+    } else if let Some(err) = err.downcast_ref::<OpenWriteError>() {
+        open_write_error(err, message)
+    } else if let Some(err) = err.downcast_ref::<io::Error>() {
+        io_error(message, Some(err))
+    } else {
+        PyErr::new::<TantivyError, _>(message)
+    }
+}
+
+fn tantivy_error(err: &tv::TantivyError, message: String) -> PyErr {
+    match err {
+        tv::TantivyError::LockFailure(LockError::LockBusy, _) => {
+            PyErr::new::<LockBusyError, _>(message)
+        }
+        tv::TantivyError::LockFailure(LockError::IoError(err), _)
+        | tv::TantivyError::IoError(err) => io_error(message, Some(err)),
+        tv::TantivyError::OpenDirectoryError(err) => {
+            open_directory_error(err, message)
+        }
+        tv::TantivyError::OpenReadError(err) => open_read_error(err, message),
+        tv::TantivyError::OpenWriteError(err) => open_write_error(err, message),
+        tv::TantivyError::DataCorruption(_) => {
+            PyErr::new::<DataCorruption, _>(message)
+        }
+        tv::TantivyError::IncompatibleIndex(_) => {
+            PyErr::new::<IncompatibleIndexError, _>(message)
+        }
+        tv::TantivyError::SchemaError(_)
+        | tv::TantivyError::FieldNotFound(_) => {
+            PyErr::new::<SchemaError, _>(message)
+        }
+        _ => PyErr::new::<TantivyError, _>(message),
+    }
+}
+
+fn open_directory_error(err: &OpenDirectoryError, message: String) -> PyErr {
+    match err {
+        OpenDirectoryError::FailedToCreateTempDir(err)
+        | OpenDirectoryError::IoError { io_error: err, .. } => {
+            io_error(message, Some(err))
+        }
+        _ => io_error(message, None),
+    }
+}
+
+fn open_read_error(err: &OpenReadError, message: String) -> PyErr {
+    match err {
+        // Only the meta file is looked up without being referenced by the
+        // index, a missing one means that there is no index.
+        OpenReadError::FileDoesNotExist(path)
+            if path != Path::new(crate::index::META_FILEPATH) =>
+        {
+            PyErr::new::<IndexCorruptedError, _>(message)
+        }
+        OpenReadError::FileDoesNotExist(_) => io_error(message, None),
+        OpenReadError::IoError { io_error: err, .. } => {
+            io_error(message, Some(err))
+        }
+        OpenReadError::IncompatibleIndex(_) => {
+            PyErr::new::<IncompatibleIndexError, _>(message)
+        }
+    }
+}
+
+fn open_write_error(err: &OpenWriteError, message: String) -> PyErr {
+    match err {
+        OpenWriteError::IoError { io_error: err, .. } => {
+            io_error(message, Some(err))
+        }
+        OpenWriteError::FileAlreadyExists(_) => io_error(message, None),
+    }
+}
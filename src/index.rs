@@ -1,70 +1,540 @@
 #![allow(clippy::new_ret_no_self)]
 
-use std::collections::HashMap;
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    ffi::CString,
+    fs::File,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Condvar, Mutex, RwLock,
+    },
+    thread::JoinHandle,
+    time::{Duration, Instant},
+};
 
-use pyo3::{exceptions, prelude::*, types::PyAny};
+use pyo3::{
+    exceptions,
+    gc::{PyTraverseError, PyVisit},
+    prelude::*,
+    types::{PyAny, PyBytes},
+};
 
 use crate::{
+    directory::PyDirectory,
     document::{extract_value, Document},
     get_field,
     parser_error::QueryParserErrorIntoPy,
     query::Query,
+    query_parser::QueryParser,
     schema::Schema,
+    schemabuilder::SchemaBuilder,
     searcher::Searcher,
     to_pyerr,
-    tokenizer::TextAnalyzer as PyTextAnalyzer,
+    tokenizer::{AnalyzerDefinition, TextAnalyzer as PyTextAnalyzer},
 };
+use serde::{Deserialize, Serialize};
 use tantivy as tv;
 use tantivy::{
-    directory::MmapDirectory,
+    directory::{
+        error::OpenReadError, Directory, FileSlice, Lock, MmapDirectory,
+        OwnedBytes, RamDirectory, INDEX_WRITER_LOCK, META_LOCK,
+    },
+    fastfield::{write_alive_bitset, AliveBitSet},
+    index::{SegmentComponent, SegmentId},
+    indexer::{MergeCandidate, MergePolicy},
+    query::{EnableScoring, Weight},
     schema::{
-        document::TantivyDocument, NamedFieldDocument, OwnedValue as Value,
-        Term,
+        document::{
+            ReferenceValue, ReferenceValueLeaf, TantivyDocument,
+            Value as DocumentValue,
+        },
+        NamedFieldDocument, OwnedValue as Value, Term,
     },
     tokenizer::{
         Language, LowerCaser, RemoveLongFilter, SimpleTokenizer, Stemmer,
         TextAnalyzer,
     },
+    HasLen, SegmentMeta,
 };
+use tantivy_common::BitSet;
 
 const RELOAD_POLICY: &str = "commit";
 
+/// Tantivy's index metadata file, holding the schema and the segment list.
+pub(crate) const META_FILEPATH: &str = "meta.json";
+
+/// Tantivy's list of the files it created, which its garbage collector may
+/// delete.
+const MANAGED_FILEPATH: &str = ".managed.json";
+
+/// Sidecar file holding the analyzers registered with `persist`. The leading
+/// dot keeps tantivy's garbage collector away from it.
+const ANALYZERS_FILEPATH: &str = ".tantivy-py-analyzers.json";
+
+/// Lock held while updating `ANALYZERS_FILEPATH`, so that processes
+/// registering analyzers at the same time do not lose each other's.
+fn analyzers_lock() -> Lock {
+    Lock {
+        filepath: PathBuf::from(".tantivy-py-analyzers.lock"),
+        is_blocking: true,
+    }
+}
+
+/// Content of the `ANALYZERS_FILEPATH` sidecar file.
+#[derive(Default, Deserialize, Serialize)]
+struct PersistedAnalyzers {
+    #[serde(default)]
+    tokenizers: BTreeMap<String, AnalyzerDefinition>,
+    #[serde(default)]
+    fast_field_tokenizers: BTreeMap<String, AnalyzerDefinition>,
+}
+
+/// The components of the segments that `Index.preload()` can load.
+const PRELOAD_COMPONENTS: [(&str, SegmentComponent); 6] = [
+    ("postings", SegmentComponent::Postings),
+    ("positions", SegmentComponent::Positions),
+    ("terms", SegmentComponent::Terms),
+    ("store", SegmentComponent::Store),
+    ("fast", SegmentComponent::FastFields),
+    ("fieldnorms", SegmentComponent::FieldNorms),
+];
+
+fn preload_component(name: &str) -> PyResult<(&'static str, SegmentComponent)> {
+    PRELOAD_COMPONENTS
+        .into_iter()
+        .find(|(component_name, _)| *component_name == name)
+        .ok_or_else(|| {
+            exceptions::PyValueError::new_err(
+                "Invalid preload component, valid choices are: 'postings', 'positions', 'terms', 'store', 'fast' and 'fieldnorms'",
+            )
+        })
+}
+
+/// Number of lines of a JSON-lines file that are parsed in parallel before
+/// being added to the index.
+const JSON_FILE_BATCH_SIZE: usize = 10_000;
+
+/// What `IndexWriter.add_json_file()` does with the lines that cannot be
+/// parsed as a document.
+#[derive(Clone, Copy)]
+enum OnError {
+    Raise,
+    Skip,
+    Collect,
+}
+
+/// Summary of an `IndexWriter.add_json_file()` call.
+#[pyclass(frozen, module = "tantivy.tantivy")]
+#[derive(Default)]
+pub(crate) struct JsonFileReport {
+    /// Number of documents added to the index.
+    #[pyo3(get)]
+    num_added: u64,
+    /// Number of lines that could not be parsed as a document.
+    #[pyo3(get)]
+    num_rejected: u64,
+    /// The `(line_number, error)` pairs of the rejected lines, only filled
+    /// when `on_error` is "collect". Line numbers start at 1.
+    #[pyo3(get)]
+    errors: Vec<(u64, String)>,
+}
+
+#[pymethods]
+impl JsonFileReport {
+    fn __repr__(&self) -> String {
+        format!(
+            "JsonFileReport(num_added={}, num_rejected={})",
+            self.num_added, self.num_rejected
+        )
+    }
+}
+
+/// Statistics about the work of an `IndexWriter`, returned by
+/// `IndexWriter.stats()`.
+#[pyclass(frozen, module = "tantivy.tantivy")]
+pub(crate) struct WriterStats {
+    /// Number of documents added since the last commit.
+    #[pyo3(get)]
+    uncommitted_docs: u64,
+    /// Number of documents added or deletions since the last commit.
+    #[pyo3(get)]
+    uncommitted_operations: u64,
+    /// The overall heap size of the writer, in bytes.
+    #[pyo3(get)]
+    heap_size: usize,
+    /// The number of indexing threads of the writer, None if it was left
+    /// for tantivy to choose.
+    #[pyo3(get)]
+    num_threads: Option<usize>,
+    /// The heap size of every indexing thread, in bytes, None if the number
+    /// of threads is unknown. A thread flushes its documents to a new segment
+    /// when its memory arena reaches it.
+    #[pyo3(get)]
+    memory_budget_per_thread: Option<usize>,
+    /// An estimate of the memory the arenas of the indexing threads
+    /// currently use, in bytes, to compare with `heap_size`. It is the size
+    /// of the values of the documents added since the last commit, less the
+    /// heap sizes the threads flushed when their arena was full, since
+    /// tantivy keeps the actual usage private to its indexing threads.
+    #[pyo3(get)]
+    memory_arena_usage: u64,
+    /// Number of committed segments in the index.
+    #[pyo3(get)]
+    num_segments: usize,
+    /// Number of committed segments that are being merged.
+    #[pyo3(get)]
+    num_merging_segments: usize,
+}
+
+#[pymethods]
+impl WriterStats {
+    fn __repr__(&self) -> String {
+        format!(
+            "WriterStats(uncommitted_docs={}, uncommitted_operations={}, num_segments={}, num_merging_segments={})",
+            self.uncommitted_docs,
+            self.uncommitted_operations,
+            self.num_segments,
+            self.num_merging_segments
+        )
+    }
+}
+
+/// Merge policy delegating to the writer's policy while recording the
+/// segments it chose to merge, since tantivy does not expose the merges in
+/// progress.
+#[derive(Debug)]
+struct MergeTrackingPolicy {
+    inner: Arc<dyn MergePolicy>,
+    merging_segments: Arc<Mutex<HashSet<SegmentId>>>,
+}
+
+impl MergePolicy for MergeTrackingPolicy {
+    fn compute_merge_candidates(
+        &self,
+        segments: &[SegmentMeta],
+    ) -> Vec<MergeCandidate> {
+        let candidates = self.inner.compute_merge_candidates(segments);
+        let mut merging_segments = self.merging_segments.lock().unwrap();
+        // The policy is only given the segments that are not being merged.
+        for segment in segments {
+            merging_segments.remove(&segment.id());
+        }
+        merging_segments.extend(
+            candidates
+                .iter()
+                .flat_map(|candidate| candidate.0.iter().copied()),
+        );
+        candidates
+    }
+}
+
 /// IndexWriter is the user entry-point to add documents to the index.
 ///
 /// To create an IndexWriter first create an Index and call the writer() method
 /// on the index object.
-#[pyclass(module = "tantivy.tantivy")]
+///
+/// An IndexWriter can be shared between Python threads. Documents can be added
+/// and deleted concurrently from several threads, while `commit()`,
+/// `rollback()` and `wait_merging_threads()` wait for the other operations to
+/// finish and block them until they are done.
+#[pyclass(frozen, module = "tantivy.tantivy")]
 pub(crate) struct IndexWriter {
-    inner_index_writer: Option<tv::IndexWriter>,
+    state: Arc<WriterState>,
     schema: tv::schema::Schema,
+    /// Number of added documents that triggers an auto-commit.
+    auto_commit_docs: Option<u64>,
+    auto_commit_thread: Mutex<Option<JoinHandle<()>>>,
+    heap_size: usize,
+    num_threads: Option<usize>,
+    /// Segments that the merge policy chose to merge, some of which may
+    /// already be merged.
+    merging_segments: Arc<Mutex<HashSet<SegmentId>>>,
 }
 
-impl IndexWriter {
-    fn inner(&self) -> PyResult<&tv::IndexWriter> {
-        self.inner_index_writer.as_ref().ok_or_else(|| {
-            exceptions::PyRuntimeError::new_err(
-                "IndexWriter was consumed and no longer in a valid state",
-            )
-        })
+/// State of an `IndexWriter` shared with its auto-commit thread.
+#[derive(Default)]
+struct WriterState {
+    inner_index_writer: RwLock<Option<tv::IndexWriter>>,
+    /// Number of documents added since the last commit.
+    uncommitted_docs: AtomicU64,
+    /// Number of documents added or deletions since the last commit.
+    uncommitted_ops: AtomicU64,
+    /// Size of the values of the documents added since the last commit, in
+    /// bytes.
+    uncommitted_bytes: AtomicU64,
+    last_auto_commit_error: Mutex<Option<String>>,
+    /// Python callable returning the payload of the auto-commits. It is
+    /// visited by the garbage collector through the `IndexWriter`, so a hook
+    /// referencing its writer does not keep it alive.
+    payload_hook: Mutex<Option<Py<PyAny>>>,
+    /// Set to stop the auto-commit thread, which waits on `wakeup`.
+    stopped: Mutex<bool>,
+    wakeup: Condvar,
+}
+
+/// Delay before the auto-commit thread retries a failed commit.
+const AUTO_COMMIT_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// When the auto-commit thread of an `IndexWriter` commits.
+struct AutoCommitPolicy {
+    docs: Option<u64>,
+    interval: Option<Duration>,
+}
+
+fn consumed_writer_error() -> PyErr {
+    exceptions::PyRuntimeError::new_err(
+        "IndexWriter was consumed and no longer in a valid state",
+    )
+}
+
+impl WriterState {
+    fn with_inner<T>(
+        &self,
+        f: impl FnOnce(&tv::IndexWriter) -> PyResult<T>,
+    ) -> PyResult<T> {
+        let inner = self.inner_index_writer.read().unwrap();
+        f(inner.as_ref().ok_or_else(consumed_writer_error)?)
     }
 
-    fn inner_mut(&mut self) -> PyResult<&mut tv::IndexWriter> {
-        self.inner_index_writer.as_mut().ok_or_else(|| {
-            exceptions::PyRuntimeError::new_err(
-                "IndexWriter was consumed and no longer in a valid state",
-            )
-        })
+    /// Runs `f` with exclusive access to the writer, the counts of
+    /// uncommitted operations are reset if it succeeds.
+    fn with_inner_mut<T>(
+        &self,
+        f: impl FnOnce(&mut tv::IndexWriter) -> PyResult<T>,
+    ) -> PyResult<T> {
+        let mut inner = self.inner_index_writer.write().unwrap();
+        let result = f(inner.as_mut().ok_or_else(consumed_writer_error)?)?;
+        self.uncommitted_docs.store(0, Ordering::SeqCst);
+        self.uncommitted_ops.store(0, Ordering::SeqCst);
+        self.uncommitted_bytes.store(0, Ordering::SeqCst);
+        Ok(result)
     }
 
-    fn take_inner(&mut self) -> PyResult<tv::IndexWriter> {
-        self.inner_index_writer.take().ok_or_else(|| {
-            exceptions::PyRuntimeError::new_err(
-                "IndexWriter was consumed and no longer in a valid state",
-            )
+    fn commit(&self, payload: Option<String>) -> PyResult<u64> {
+        self.with_inner_mut(|writer| {
+            let mut prepared_commit =
+                writer.prepare_commit().map_err(to_pyerr)?;
+            if let Some(payload) = payload {
+                prepared_commit.set_payload(&payload);
+            }
+            prepared_commit.commit().map_err(to_pyerr)
         })
     }
 }
 
+impl IndexWriter {
+    fn new(
+        writer: tv::IndexWriter,
+        schema: tv::schema::Schema,
+        heap_size: usize,
+        num_threads: Option<usize>,
+        auto_commit: Option<AutoCommitPolicy>,
+        payload_hook: Option<Py<PyAny>>,
+    ) -> Self {
+        let merging_segments = Arc::default();
+        writer.set_merge_policy(Box::new(MergeTrackingPolicy {
+            inner: writer.get_merge_policy(),
+            merging_segments: Arc::clone(&merging_segments),
+        }));
+        let state = Arc::new(WriterState {
+            inner_index_writer: RwLock::new(Some(writer)),
+            payload_hook: Mutex::new(payload_hook),
+            ..WriterState::default()
+        });
+        let auto_commit_docs = auto_commit.as_ref().and_then(|p| p.docs);
+        let auto_commit_thread = auto_commit.map(|policy| {
+            let state = state.clone();
+            std::thread::spawn(move || run_auto_commit(&state, policy))
+        });
+        IndexWriter {
+            state,
+            schema,
+            auto_commit_docs,
+            auto_commit_thread: Mutex::new(auto_commit_thread),
+            heap_size,
+            num_threads,
+            merging_segments,
+        }
+    }
+
+    /// Runs `f` with shared access to the writer, which tantivy allows for
+    /// adding and deleting documents.
+    ///
+    /// The lock must be taken without holding the GIL.
+    fn with_inner<T>(
+        &self,
+        f: impl FnOnce(&tv::IndexWriter) -> PyResult<T>,
+    ) -> PyResult<T> {
+        self.state.with_inner(f)
+    }
+
+    /// Counts operations that are not committed yet, waking the auto-commit
+    /// thread up when enough documents were added. Must be called while
+    /// holding the writer lock, so that a commit cannot happen in between
+    /// the operations and their count.
+    fn record_uncommitted(&self, num_docs: u64, num_ops: u64, num_bytes: u64) {
+        let previous_docs = self
+            .state
+            .uncommitted_docs
+            .fetch_add(num_docs, Ordering::SeqCst);
+        self.state
+            .uncommitted_ops
+            .fetch_add(num_ops, Ordering::SeqCst);
+        self.state
+            .uncommitted_bytes
+            .fetch_add(num_bytes, Ordering::SeqCst);
+        if self.auto_commit_docs.is_some_and(|docs| {
+            previous_docs < docs && previous_docs + num_docs >= docs
+        }) {
+            let _stopped = self.state.stopped.lock().unwrap();
+            self.state.wakeup.notify_all();
+        }
+    }
+
+    fn take_inner(&self) -> PyResult<tv::IndexWriter> {
+        self.state
+            .inner_index_writer
+            .write()
+            .unwrap()
+            .take()
+            .ok_or_else(consumed_writer_error)
+    }
+
+    /// Stops the auto-commit thread, if any, and waits for it to finish.
+    fn stop_auto_commit(&self) {
+        let Some(handle) = self.auto_commit_thread.lock().unwrap().take()
+        else {
+            return;
+        };
+        *self.state.stopped.lock().unwrap() = true;
+        self.state.wakeup.notify_all();
+        // The thread may need the GIL to call the payload hook, so it is
+        // released while waiting.
+        let mut handle = Some(handle);
+        Python::try_attach(|py| {
+            py.detach(|| handle.take().map(|handle| handle.join()))
+        });
+        if let Some(handle) = handle {
+            let _ = handle.join();
+        }
+    }
+}
+
+impl Drop for IndexWriter {
+    fn drop(&mut self) {
+        self.stop_auto_commit();
+        // Dropping the writer joins its indexing threads, which may need the
+        // GIL to write their segment through a Python directory.
+        let mut inner = self.state.inner_index_writer.write().unwrap().take();
+        Python::try_attach(|py| py.detach(|| drop(inner.take())));
+    }
+}
+
+/// Estimates the memory used to index a document, in bytes: the length of
+/// its texts, bytes and keys, and eight bytes per other value.
+fn document_mem_usage(doc: &TantivyDocument) -> u64 {
+    doc.field_values()
+        .map(|(_, value)| value_mem_usage(&value))
+        .sum()
+}
+
+fn value_mem_usage<'a, V: DocumentValue<'a>>(value: &V) -> u64 {
+    match value.as_value() {
+        ReferenceValue::Leaf(ReferenceValueLeaf::Null) => 0,
+        ReferenceValue::Leaf(
+            ReferenceValueLeaf::Str(text) | ReferenceValueLeaf::Facet(text),
+        ) => text.len() as u64,
+        ReferenceValue::Leaf(ReferenceValueLeaf::Bytes(bytes)) => {
+            bytes.len() as u64
+        }
+        ReferenceValue::Leaf(ReferenceValueLeaf::PreTokStr(text)) => {
+            text.text.len() as u64
+        }
+        ReferenceValue::Leaf(_) => 8,
+        ReferenceValue::Array(values) => {
+            values.map(|value| value_mem_usage(&value)).sum()
+        }
+        ReferenceValue::Object(entries) => entries
+            .map(|(key, value)| key.len() as u64 + value_mem_usage(&value))
+            .sum(),
+    }
+}
+
+/// Body of the auto-commit thread of an `IndexWriter`, which commits when
+/// enough documents were added or when the interval elapsed, until the
+/// writer is stopped.
+fn run_auto_commit(state: &WriterState, policy: AutoCommitPolicy) {
+    let mut deadline =
+        policy.interval.map(|interval| Instant::now() + interval);
+    loop {
+        let mut stopped = state.stopped.lock().unwrap();
+        loop {
+            if *stopped {
+                return;
+            }
+            if policy.docs.is_some_and(|docs| {
+                state.uncommitted_docs.load(Ordering::SeqCst) >= docs
+            }) {
+                break;
+            }
+            let now = Instant::now();
+            stopped = match deadline {
+                Some(deadline) if deadline <= now => break,
+                Some(deadline) => {
+                    state
+                        .wakeup
+                        .wait_timeout(stopped, deadline - now)
+                        .unwrap()
+                        .0
+                }
+                None => state.wakeup.wait(stopped).unwrap(),
+            };
+        }
+        drop(stopped);
+
+        deadline = policy.interval.map(|interval| Instant::now() + interval);
+        if state.uncommitted_ops.load(Ordering::SeqCst) == 0 {
+            continue;
+        }
+        let payload = if state.payload_hook.lock().unwrap().is_some() {
+            // The interpreter is shutting down.
+            let Some(payload) = Python::try_attach(|py| {
+                let hook = state
+                    .payload_hook
+                    .lock()
+                    .unwrap()
+                    .as_ref()
+                    .map(|hook| hook.clone_ref(py));
+                match hook {
+                    Some(hook) => hook.call0(py)?.extract::<Option<String>>(py),
+                    None => Ok(None),
+                }
+            }) else {
+                return;
+            };
+            payload
+        } else {
+            Ok(None)
+        };
+        if let Err(err) = payload.and_then(|payload| state.commit(payload)) {
+            *state.last_auto_commit_error.lock().unwrap() =
+                Some(err.to_string());
+            // Wait before retrying, the documents threshold is still reached.
+            let stopped = state.stopped.lock().unwrap();
+            let _ = state
+                .wakeup
+                .wait_timeout_while(
+                    stopped,
+                    AUTO_COMMIT_RETRY_DELAY,
+                    |stopped| !*stopped,
+                )
+                .unwrap();
+        }
+    }
+}
+
 #[pymethods]
 impl IndexWriter {
     /// Add a document to the index.
@@ -75,17 +545,18 @@ impl IndexWriter {
     /// by the client to align commits with its own document queue.
     /// The `opstamp` represents the number of documents that have been added
     /// since the creation of the index.
-    pub fn add_document(
-        &mut self,
-        py: Python,
-        doc: &Document,
-    ) -> PyResult<u64> {
+    pub fn add_document(&self, py: Python, doc: &Document) -> PyResult<u64> {
         py.detach(move || {
             let named_doc = NamedFieldDocument(doc.field_values.clone());
             let doc =
                 TantivyDocument::convert_named_doc(&self.schema, named_doc)
                     .map_err(to_pyerr)?;
-            self.inner()?.add_document(doc).map_err(to_pyerr)
+            let num_bytes = document_mem_usage(&doc);
+            self.with_inner(|writer| {
+                let opstamp = writer.add_document(doc).map_err(to_pyerr)?;
+                self.record_uncommitted(1, 1, num_bytes);
+                Ok(opstamp)
+            })
         })
     }
 
@@ -97,12 +568,103 @@ impl IndexWriter {
     /// by the client to align commits with its own document queue.
     /// The `opstamp` represents the number of documents that have been added
     /// since the creation of the index.
-    pub fn add_json(&mut self, py: Python, json: &str) -> PyResult<u64> {
+    pub fn add_json(&self, py: Python, json: &str) -> PyResult<u64> {
         py.detach(move || {
             let doc = TantivyDocument::parse_json(&self.schema, json)
                 .map_err(to_pyerr)?;
-            let opstamp = self.inner()?.add_document(doc);
-            opstamp.map_err(to_pyerr)
+            let num_bytes = document_mem_usage(&doc);
+            self.with_inner(|writer| {
+                let opstamp = writer.add_document(doc).map_err(to_pyerr)?;
+                self.record_uncommitted(1, 1, num_bytes);
+                Ok(opstamp)
+            })
+        })
+    }
+
+    /// Add the documents of a JSON-lines file, holding one JSON object per
+    /// line.
+    ///
+    /// The file is streamed and its lines are parsed in parallel, without
+    /// holding the GIL. The documents are added in the order of the lines
+    /// and blank lines are ignored.
+    ///
+    /// Args:
+    ///     path (str): The path of the file.
+    ///     on_error (str, optional): What to do with a line that cannot be
+    ///         parsed as a document. Can be one of 'raise', 'skip' or
+    ///         'collect'. With 'raise', a ValueError is raised and the
+    ///         documents of the lines after the invalid one are not added.
+    ///         With 'skip' the line is ignored, and with 'collect' it is
+    ///         also reported in the `errors` of the result. Defaults to
+    ///         'raise'.
+    ///
+    /// Returns a `JsonFileReport` with the number of added and rejected
+    /// documents.
+    /// Raises a ValueError if the file cannot be read.
+    #[pyo3(signature = (path, on_error = "raise"))]
+    fn add_json_file(
+        &self,
+        py: Python,
+        path: &str,
+        on_error: &str,
+    ) -> PyResult<JsonFileReport> {
+        let on_error = match on_error {
+            "raise" => OnError::Raise,
+            "skip" => OnError::Skip,
+            "collect" => OnError::Collect,
+            _ => return Err(exceptions::PyValueError::new_err(
+                "Invalid on_error, valid choices are: 'raise', 'skip' and 'collect'"
+            ))
+        };
+        py.detach(move || {
+            let mut reader =
+                BufReader::new(File::open(path).map_err(to_pyerr)?);
+            let mut report = JsonFileReport::default();
+            let mut line_number = 0;
+            loop {
+                let mut lines = Vec::with_capacity(JSON_FILE_BATCH_SIZE);
+                while lines.len() < JSON_FILE_BATCH_SIZE {
+                    let mut line = Vec::new();
+                    if reader.read_until(b'\n', &mut line).map_err(to_pyerr)?
+                        == 0
+                    {
+                        break;
+                    }
+                    line_number += 1;
+                    if !line.iter().all(u8::is_ascii_whitespace) {
+                        lines.push((line_number, line));
+                    }
+                }
+                if lines.is_empty() {
+                    break;
+                }
+
+                let docs = parse_json_lines(&self.schema, &lines);
+                self.with_inner(|writer| {
+                    for (line_number, doc) in docs {
+                        match (doc, on_error) {
+                            (Ok(doc), _) => {
+                                let num_bytes = document_mem_usage(&doc);
+                                writer.add_document(doc).map_err(to_pyerr)?;
+                                self.record_uncommitted(1, 1, num_bytes);
+                                report.num_added += 1;
+                            }
+                            (Err(err), OnError::Raise) => {
+                                return Err(exceptions::PyValueError::new_err(
+                                    format!("Line {line_number}: {err}"),
+                                ))
+                            }
+                            (Err(_), OnError::Skip) => report.num_rejected += 1,
+                            (Err(err), OnError::Collect) => {
+                                report.num_rejected += 1;
+                                report.errors.push((line_number, err));
+                            }
+                        }
+                    }
+                    Ok(())
+                })?;
+            }
+            Ok(report)
         })
     }
 
@@ -115,8 +677,8 @@ impl IndexWriter {
     /// spared), it will be possible to resume indexing from this point.
     ///
     /// Returns the `opstamp` of the last document that made it in the commit.
-    fn commit(&mut self, py: Python) -> PyResult<u64> {
-        py.detach(move || self.inner_mut()?.commit().map_err(to_pyerr))
+    fn commit(&self, py: Python) -> PyResult<u64> {
+        py.detach(move || self.state.commit(None))
     }
 
     /// Rollback to the last commit
@@ -124,25 +686,32 @@ impl IndexWriter {
     /// This cancels all of the update that happened before after the last
     /// commit. After calling rollback, the index is in the same state as it
     /// was after the last commit.
-    fn rollback(&mut self, py: Python) -> PyResult<u64> {
-        py.detach(move || self.inner_mut()?.rollback().map_err(to_pyerr))
+    fn rollback(&self, py: Python) -> PyResult<u64> {
+        py.detach(move || {
+            self.state
+                .with_inner_mut(|writer| writer.rollback().map_err(to_pyerr))
+        })
     }
 
     /// Detect and removes the files that are not used by the index anymore.
-    fn garbage_collect_files(&mut self, py: Python) -> PyResult<()> {
+    fn garbage_collect_files(&self, py: Python) -> PyResult<()> {
         py.detach(move || {
             use futures::executor::block_on;
-            block_on(self.inner()?.garbage_collect_files())
-                .map_err(to_pyerr)?;
-            Ok(())
+            self.with_inner(|writer| {
+                block_on(writer.garbage_collect_files()).map_err(to_pyerr)?;
+                Ok(())
+            })
         })
     }
 
     /// Deletes all documents from the index.
-    fn delete_all_documents(&mut self, py: Python) -> PyResult<()> {
+    fn delete_all_documents(&self, py: Python) -> PyResult<()> {
         py.detach(move || {
-            self.inner()?.delete_all_documents().map_err(to_pyerr)?;
-            Ok(())
+            self.with_inner(|writer| {
+                writer.delete_all_documents().map_err(to_pyerr)?;
+                self.record_uncommitted(0, 1, 0);
+                Ok(())
+            })
         })
     }
 
@@ -155,14 +724,73 @@ impl IndexWriter {
     /// for searchers.
     #[getter]
     fn commit_opstamp(&self, py: Python) -> PyResult<u64> {
-        py.detach(move || Ok(self.inner()?.commit_opstamp()))
+        py.detach(move || self.with_inner(|writer| Ok(writer.commit_opstamp())))
+    }
+
+    /// The error of the last auto-commit that failed, if any.
+    ///
+    /// Auto-commits run in a background thread, so their errors cannot be
+    /// raised. The error is kept after later auto-commits succeed.
+    #[getter]
+    fn last_auto_commit_error(&self) -> Option<String> {
+        self.state.last_auto_commit_error.lock().unwrap().clone()
+    }
+
+    /// Statistics about the operations pending in the writer and the
+    /// segments of the index.
+    ///
+    /// Returns:
+    ///     WriterStats: The number of uncommitted documents and operations,
+    ///         the heap size and number of threads of the writer, the
+    ///         estimated memory used by the indexing threads, and the number
+    ///         of committed segments and of those being merged. The number
+    ///         of threads is only known when it was given to
+    ///         `Index.writer()`.
+    fn stats(&self, py: Python) -> PyResult<WriterStats> {
+        py.detach(move || {
+            self.with_inner(|writer| {
+                let segment_ids = writer
+                    .index()
+                    .searchable_segment_ids()
+                    .map_err(to_pyerr)?;
+                let merging_segments = self.merging_segments.lock().unwrap();
+                let num_merging_segments = segment_ids
+                    .iter()
+                    .filter(|segment_id| merging_segments.contains(segment_id))
+                    .count();
+                Ok(WriterStats {
+                    uncommitted_docs: self
+                        .state
+                        .uncommitted_docs
+                        .load(Ordering::SeqCst),
+                    uncommitted_operations: self
+                        .state
+                        .uncommitted_ops
+                        .load(Ordering::SeqCst),
+                    heap_size: self.heap_size,
+                    num_threads: self.num_threads,
+                    memory_budget_per_thread: self
+                        .num_threads
+                        .map(|num_threads| self.heap_size / num_threads),
+                    // The documents are spread over the threads, so the
+                    // arenas flush every `heap_size` bytes overall.
+                    memory_arena_usage: self
+                        .state
+                        .uncommitted_bytes
+                        .load(Ordering::SeqCst)
+                        % self.heap_size.max(1) as u64,
+                    num_segments: segment_ids.len(),
+                    num_merging_segments,
+                })
+            })
+        })
     }
 
     /// Deprecated alias of `delete_documents_by_term`. Calling it emits a
     /// `DeprecationWarning`; use `delete_documents_by_term` or
     /// `delete_documents_by_query` instead.
     fn delete_documents(
-        &mut self,
+        &self,
         py: Python,
         field_name: &str,
         field_value: &Bound<PyAny>,
@@ -198,7 +826,7 @@ impl IndexWriter {
     /// If the field_name is not on the schema raises ValueError exception.
     /// If the field_value is not supported raises Exception.
     fn delete_documents_by_term(
-        &mut self,
+        &self,
         py: Python,
         field_name: &str,
         field_value: &Bound<PyAny>,
@@ -241,7 +869,11 @@ impl IndexWriter {
                 Value::Bool(b) => Term::from_field_bool(field, b),
                 Value::IpAddr(i) => Term::from_field_ip_addr(field, i)
             };
-            Ok(self.inner()?.delete_term(term))
+            self.with_inner(|writer| {
+                let opstamp = writer.delete_term(term);
+                self.record_uncommitted(0, 1, 0);
+                Ok(opstamp)
+            })
         })
     }
 
@@ -275,12 +907,18 @@ impl IndexWriter {
     /// If the query is not valid raises ValueError exception.
     /// If the query is not supported raises Exception.
     fn delete_documents_by_query(
-        &mut self,
+        &self,
         py: Python,
         query: &Query,
     ) -> PyResult<u64> {
         let q = query.inner.box_clone();
-        py.detach(move || self.inner()?.delete_query(q).map_err(to_pyerr))
+        py.detach(move || {
+            self.with_inner(|writer| {
+                let opstamp = writer.delete_query(q).map_err(to_pyerr)?;
+                self.record_uncommitted(0, 1, 0);
+                Ok(opstamp)
+            })
+        })
     }
 
     /// If there are some merging threads, blocks until they all finish
@@ -288,18 +926,28 @@ impl IndexWriter {
     ///
     /// This will consume the `IndexWriter`. Further accesses to the
     /// object will result in an error.
-    pub fn wait_merging_threads(&mut self, py: Python) -> PyResult<()> {
+    pub fn wait_merging_threads(&self, py: Python) -> PyResult<()> {
+        self.stop_auto_commit();
         py.detach(move || {
             self.take_inner()?.wait_merging_threads().map_err(to_pyerr)
         })
     }
 
+    fn __traverse__(&self, visit: PyVisit<'_>) -> Result<(), PyTraverseError> {
+        // The lock is never held while waiting for the GIL.
+        visit.call(self.state.payload_hook.lock().unwrap().as_ref())
+    }
+
+    fn __clear__(&self) {
+        self.state.payload_hook.lock().unwrap().take();
+    }
+
     pub fn __enter__(slf: Py<Self>) -> Py<Self> {
         slf
     }
 
     pub fn __exit__(
-        &mut self,
+        &self,
         py: Python,
         _exc_type: Py<PyAny>,
         _exc_value: Py<PyAny>,
@@ -310,6 +958,233 @@ impl IndexWriter {
     }
 }
 
+/// Result of an integrity verification, see `Index.verify()`.
+#[pyclass(frozen, module = "tantivy.tantivy")]
+#[derive(Default)]
+pub(crate) struct VerificationReport {
+    /// Number of segments listed in the index metadata.
+    #[pyo3(get)]
+    num_segments: usize,
+    /// Number of segment files that were checked.
+    #[pyo3(get)]
+    num_files: usize,
+    /// The files listed in the index metadata that do not exist.
+    #[pyo3(get)]
+    missing_files: Vec<String>,
+    /// The files whose content does not match their checksum or that could
+    /// not be read.
+    #[pyo3(get)]
+    damaged_files: Vec<String>,
+}
+
+#[pymethods]
+impl VerificationReport {
+    /// Whether no file is missing or damaged.
+    #[getter]
+    fn is_valid(&self) -> bool {
+        self.missing_files.is_empty() && self.damaged_files.is_empty()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "VerificationReport(num_segments={}, num_files={}, num_missing_files={}, num_damaged_files={})",
+            self.num_segments,
+            self.num_files,
+            self.missing_files.len(),
+            self.damaged_files.len()
+        )
+    }
+}
+
+/// The files of a segment.
+fn segment_files(
+    segment_meta: &SegmentMeta,
+) -> impl Iterator<Item = PathBuf> + '_ {
+    // Unlike the other components, the delete file only exists once
+    // documents of the segment were deleted.
+    SegmentComponent::iterator()
+        .filter(|component| {
+            **component != SegmentComponent::Delete
+                || segment_meta.has_deletes()
+        })
+        .map(|component| segment_meta.relative_path(*component))
+}
+
+/// The part of `meta.json` listing the segments of a commit.
+#[derive(Deserialize)]
+struct CommitMeta {
+    segments: Vec<CommitSegmentMeta>,
+    opstamp: tv::Opstamp,
+}
+
+#[derive(Deserialize)]
+struct CommitSegmentMeta {
+    segment_id: SegmentId,
+    max_doc: u32,
+    deletes: Option<CommitDeleteMeta>,
+}
+
+#[derive(Deserialize)]
+struct CommitDeleteMeta {
+    num_deleted_docs: u32,
+    opstamp: tv::Opstamp,
+}
+
+impl CommitMeta {
+    /// Whether `generation` searches the segments of the commit, along with
+    /// their deletes.
+    fn is_searched_by(&self, generation: &tv::SearcherGeneration) -> bool {
+        let segments: BTreeMap<_, _> = self
+            .segments
+            .iter()
+            .map(|segment| {
+                let deletes = segment.deletes.as_ref();
+                (segment.segment_id, deletes.map(|deletes| deletes.opstamp))
+            })
+            .collect();
+        &segments == generation.segments()
+    }
+
+    /// The files of the segments of the commit.
+    fn files(&self, index: &tv::Index) -> tv::Result<Vec<PathBuf>> {
+        let mut paths = Vec::new();
+        for segment in &self.segments {
+            let mut segment_meta =
+                index.new_segment_meta(segment.segment_id, segment.max_doc);
+            if let Some(deletes) = &segment.deletes {
+                if deletes.num_deleted_docs > segment.max_doc {
+                    return Err(tv::TantivyError::DataCorruption(
+                        tv::error::DataCorruption::new(
+                            PathBuf::from(META_FILEPATH),
+                            "A segment has more deleted documents than documents."
+                                .to_string(),
+                        ),
+                    ));
+                }
+                segment_meta = segment_meta.with_delete_meta(
+                    deletes.num_deleted_docs,
+                    deletes.opstamp,
+                );
+            }
+            paths.extend(segment_files(&segment_meta));
+        }
+        Ok(paths)
+    }
+}
+
+/// The last commit of an index, read while its files were protected from
+/// garbage collection.
+struct LastCommit {
+    opstamp: tv::Opstamp,
+    /// Content of `meta.json`.
+    meta: Vec<u8>,
+    /// The files of the segments of the commit.
+    files: Vec<PathBuf>,
+    /// The files that must be copied, opened files can still be read once
+    /// deleted.
+    opened_files: Vec<(PathBuf, FileSlice)>,
+    /// Content of the `ANALYZERS_FILEPATH` sidecar file, if any.
+    analyzers: Option<Vec<u8>>,
+}
+
+impl LastCommit {
+    /// Calls `write` with the files to write to copy the commit, `meta.json`
+    /// being the last one so that an interrupted copy is not an index.
+    fn write_to(
+        self,
+        mut write: impl FnMut(&Path, &[u8]) -> PyResult<()>,
+    ) -> PyResult<()> {
+        for (path, file) in &self.opened_files {
+            let bytes = file.read_bytes().map_err(to_pyerr)?;
+            write(path, bytes.as_slice())?;
+        }
+        if let Some(analyzers) = &self.analyzers {
+            write(Path::new(ANALYZERS_FILEPATH), analyzers)?;
+        }
+        // Lets the garbage collection of the copy delete the segment files
+        // once they are merged.
+        let managed_files =
+            serde_json::to_vec(&self.files).map_err(to_pyerr)?;
+        write(Path::new(MANAGED_FILEPATH), &managed_files)?;
+        write(Path::new(META_FILEPATH), &self.meta)
+    }
+}
+
+/// Magic bytes starting the archives of `Index.to_bytes()`, followed by
+/// the files of the index. Each file is stored as the length of its name
+/// (u32), its name, the length of its content (u64) and its content, all the
+/// integers are little-endian.
+const ARCHIVE_MAGIC: &[u8] = b"tantivy-py-index-v1";
+
+/// The `(name, content)` pairs of the files of an `Index.to_bytes()`
+/// archive, or None if the archive is malformed.
+fn unpack_archive(data: &[u8]) -> Option<Vec<(&str, &[u8])>> {
+    fn take<'a>(data: &mut &'a [u8], len: usize) -> Option<&'a [u8]> {
+        let (head, tail) = data.split_at_checked(len)?;
+        *data = tail;
+        Some(head)
+    }
+
+    let mut data = data.strip_prefix(ARCHIVE_MAGIC)?;
+    let mut files = Vec::new();
+    while !data.is_empty() {
+        let name_len = u32::from_le_bytes(take(&mut data, 4)?.try_into().ok()?);
+        let name =
+            std::str::from_utf8(take(&mut data, name_len as usize)?).ok()?;
+        // Only plain file names are written in archives.
+        if Path::new(name).file_name() != Some(name.as_ref()) {
+            return None;
+        }
+        let len = u64::from_le_bytes(take(&mut data, 8)?.try_into().ok()?);
+        files.push((name, take(&mut data, usize::try_from(len).ok()?)?));
+    }
+    Some(files)
+}
+
+/// Checks that the files of the segments listed in the metadata of the index
+/// exist and match their checksum.
+fn verify_index(index: &tv::Index) -> tv::Result<VerificationReport> {
+    let segment_metas = index.searchable_segment_metas()?;
+    let mut paths: Vec<_> =
+        segment_metas.iter().flat_map(segment_files).collect();
+    paths.sort();
+    let directory = index.directory();
+    let mut report = VerificationReport {
+        num_segments: segment_metas.len(),
+        num_files: paths.len(),
+        ..VerificationReport::default()
+    };
+    for path in paths {
+        if !directory.exists(&path)? {
+            report
+                .missing_files
+                .push(path.to_string_lossy().into_owned());
+        } else if !directory.validate_checksum(&path).unwrap_or(false) {
+            report
+                .damaged_files
+                .push(path.to_string_lossy().into_owned());
+        }
+    }
+    Ok(report)
+}
+
+/// Returns the documents of `segment` that do not match `weight`.
+fn unmatched_docs(
+    segment: &tv::Segment,
+    weight: &dyn Weight,
+) -> tv::Result<AliveBitSet> {
+    let reader = tv::SegmentReader::open(segment)?;
+    let mut alive = BitSet::with_max_value_and_full(reader.max_doc());
+    weight.for_each_no_score(&reader, &mut |docs| {
+        for &doc in docs {
+            alive.remove(doc);
+        }
+    })?;
+    let mut bytes = Vec::new();
+    write_alive_bitset(&alive, &mut bytes)?;
+    Ok(AliveBitSet::open(OwnedBytes::new(bytes)))
+}
+
 /// Create a new index object.
 ///
 /// Args:
@@ -318,6 +1193,17 @@ impl IndexWriter {
 ///         no path is provided, the index will be stored in memory.
 ///     reuse (bool, optional): Should we open an existing index if one exists
 ///         or always create a new one.
+///     directory (object, optional): An object storing the files of the
+///         index instead of `path`, for instance in a blob storage. It must
+///         have the methods `read_range(path, start, end) -> bytes`,
+///         `file_size(path) -> int`, `write(path, data)`,
+///         `atomic_write(path, data)` and `delete(path)`. `read_range`,
+///         `file_size` and `delete` must raise `FileNotFoundError` for
+///         missing files. The methods are called from the threads of
+///         tantivy.
+///     read_cache_size (int, optional): The number of bytes read from
+///         `directory` to keep in memory, to avoid reading the most used
+///         parts of the index again.
 ///
 /// If an index already exists it will be opened and reused. Raises OSError
 /// if there was a problem during the opening or creation of the index.
@@ -325,55 +1211,142 @@ impl IndexWriter {
 pub(crate) struct Index {
     pub(crate) index: tv::Index,
     reader: tv::IndexReader,
+    /// The directory the index was opened from, before tantivy wraps it in a
+    /// `ManagedDirectory`. Used to reopen the index after its metadata
+    /// changed.
+    directory: Box<dyn Directory>,
+    /// The path of the index directory, if it is stored on disk.
+    path: Option<PathBuf>,
+    read_only: bool,
+    /// Number of bytes of every component loaded by the `preload` argument
+    /// of `Index.open()`.
+    preloaded_bytes: BTreeMap<&'static str, u64>,
+    /// The size of the read cache of the Python directory of the index, if
+    /// it is stored through one.
+    read_cache_size: Option<usize>,
 }
 
 #[pymethods]
 impl Index {
+    /// Open an existing index.
+    ///
+    /// Args:
+    ///     path (str, optional): The directory containing the index.
+    ///     directory (object, optional): The object storing the files of the
+    ///         index, instead of `path`. See `Index()` for its methods.
+    ///     read_cache_size (int, optional): The number of bytes read from
+    ///         `directory` to keep in memory.
+    ///     read_only (bool, optional): Refuse to create writers, add fields
+    ///         or persist analyzers.
+    ///     preload (List[str], optional): The components of the segments to
+    ///         load up front, so that the first searches do not wait for the
+    ///         disk. See `preload()` for the valid components. The number of
+    ///         bytes loaded is available from `preloaded_bytes`.
     #[staticmethod]
-    fn open(py: Python, path: &str) -> PyResult<Index> {
+    #[pyo3(signature = (
+        path = None,
+        directory = None,
+        read_cache_size = 0,
+        read_only = false,
+        preload = Vec::new()
+    ))]
+    fn open(
+        py: Python,
+        path: Option<&str>,
+        directory: Option<Py<PyAny>>,
+        read_cache_size: usize,
+        read_only: bool,
+        preload: Vec<String>,
+    ) -> PyResult<Index> {
+        if path.is_none() && directory.is_none() {
+            return Err(exceptions::PyValueError::new_err(
+                "Either `path` or `directory` must be given.",
+            ));
+        }
+        let preload = preload
+            .iter()
+            .map(|name| preload_component(name))
+            .collect::<PyResult<Vec<_>>>()?;
         py.detach(move || {
-            let index = tv::Index::open_in_dir(path).map_err(to_pyerr)?;
+            let read_cache_size =
+                directory.is_some().then_some(read_cache_size);
+            let directory = Index::open_directory(
+                path,
+                directory,
+                read_cache_size.unwrap_or_default(),
+            )?;
+            let index =
+                tv::Index::open(directory.box_clone()).map_err(to_pyerr)?;
 
             Index::register_custom_text_analyzers(&index);
+            Index::register_persisted_analyzers(&index)?;
 
             let reader = index.reader().map_err(to_pyerr)?;
-            Ok(Index { index, reader })
+            let mut index = Index {
+                index,
+                reader,
+                directory,
+                path: path.map(PathBuf::from),
+                read_only,
+                preloaded_bytes: BTreeMap::new(),
+                read_cache_size,
+            };
+            index.preloaded_bytes = index.preload_components(&preload)?;
+            Ok(index)
         })
     }
 
     #[new]
-    #[pyo3(signature = (schema, path = None, reuse = true))]
+    #[pyo3(signature = (
+        schema,
+        path = None,
+        reuse = true,
+        directory = None,
+        read_cache_size = 0
+    ))]
     fn new(
         py: Python,
         schema: &Schema,
         path: Option<&str>,
         reuse: bool,
+        directory: Option<Py<PyAny>>,
+        read_cache_size: usize,
     ) -> PyResult<Self> {
         py.detach(move || {
-            let index = match path {
-                Some(p) => {
-                    let directory = MmapDirectory::open(p).map_err(to_pyerr)?;
-                    if reuse {
-                        tv::Index::open_or_create(
-                            directory,
-                            schema.inner.clone(),
-                        )
-                    } else {
-                        tv::Index::create(
-                            directory,
-                            schema.inner.clone(),
-                            tv::IndexSettings::default(),
-                        )
-                    }
-                    .map_err(to_pyerr)?
-                }
-                None => tv::Index::create_in_ram(schema.inner.clone()),
-            };
+            let read_cache_size =
+                directory.is_some().then_some(read_cache_size);
+            let directory = Index::open_directory(
+                path,
+                directory,
+                read_cache_size.unwrap_or_default(),
+            )?;
+            let index = if reuse {
+                tv::Index::open_or_create(
+                    directory.box_clone(),
+                    schema.inner.clone(),
+                )
+            } else {
+                tv::Index::create(
+                    directory.box_clone(),
+                    schema.inner.clone(),
+                    tv::IndexSettings::default(),
+                )
+            }
+            .map_err(to_pyerr)?;
 
             Index::register_custom_text_analyzers(&index);
+            Index::register_persisted_analyzers(&index)?;
 
             let reader = index.reader().map_err(to_pyerr)?;
-            Ok(Index { index, reader })
+            Ok(Index {
+                index,
+                reader,
+                directory,
+                path: path.map(PathBuf::from),
+                read_only: false,
+                preloaded_bytes: BTreeMap::new(),
+                read_cache_size,
+            })
         })
     }
 
@@ -393,26 +1366,95 @@ impl Index {
     ///     num_threads (int, optional): The number of threads that the writer
     ///         should use. If this value is 0, tantivy will choose
     ///         automatically the number of threads.
+    ///     auto_commit_docs (int, optional): Commit automatically, from a
+    ///         background thread, once this number of documents were added
+    ///         since the last commit.
+    ///     auto_commit_interval (float, optional): Commit automatically, from
+    ///         a background thread, every given number of seconds if
+    ///         documents were added or deleted since the last commit.
+    ///     auto_commit_payload (Callable[[], Optional[str]], optional): Called
+    ///         before every auto-commit, the returned string is stored as the
+    ///         payload of the commit. The hook may reference the writer, the
+    ///         garbage collector then collects them together.
+    ///
+    /// The errors of the auto-commits are available from the
+    /// `last_auto_commit_error` property of the writer.
     ///
     /// Raises ValueError if there was an error while creating the writer.
-    #[pyo3(signature = (heap_size = 128_000_000, num_threads = 0))]
+    #[pyo3(signature = (
+        heap_size = 128_000_000,
+        num_threads = 0,
+        auto_commit_docs = None,
+        auto_commit_interval = None,
+        auto_commit_payload = None
+    ))]
     fn writer(
         &self,
         py: Python,
         heap_size: usize,
         num_threads: usize,
+        auto_commit_docs: Option<u64>,
+        auto_commit_interval: Option<f64>,
+        auto_commit_payload: Option<Py<PyAny>>,
     ) -> PyResult<IndexWriter> {
-        py.detach(move || {
-            let writer = match num_threads {
-                0 => self.index.writer(heap_size),
-                _ => self.index.writer_with_num_threads(num_threads, heap_size),
+        self.check_writable()?;
+        if auto_commit_docs == Some(0) {
+            return Err(exceptions::PyValueError::new_err(
+                "auto_commit_docs must be greater than 0.",
+            ));
+        }
+        let auto_commit_interval = auto_commit_interval
+            .map(|seconds| {
+                Duration::try_from_secs_f64(seconds)
+                    .ok()
+                    .filter(|interval| !interval.is_zero())
+                    .ok_or_else(|| {
+                        exceptions::PyValueError::new_err(
+                            "auto_commit_interval must be a positive number of seconds.",
+                        )
+                    })
+            })
+            .transpose()?;
+        if let Some(payload) = &auto_commit_payload {
+            if !payload.bind(py).is_callable() {
+                return Err(exceptions::PyValueError::new_err(
+                    "auto_commit_payload must be callable.",
+                ));
             }
-            .map_err(to_pyerr)?;
+        }
+        let auto_commit = if auto_commit_docs.is_some()
+            || auto_commit_interval.is_some()
+        {
+            Some(AutoCommitPolicy {
+                docs: auto_commit_docs,
+                interval: auto_commit_interval,
+            })
+        } else if auto_commit_payload.is_some() {
+            return Err(exceptions::PyValueError::new_err(
+                "auto_commit_payload requires auto_commit_docs or auto_commit_interval.",
+            ));
+        } else {
+            None
+        };
+        py.detach(move || {
+            let (writer, num_threads) = match num_threads {
+                // Tantivy does not tell the number of threads it picked.
+                0 => (self.index.writer(heap_size), None),
+                _ => (
+                    self.index.writer_with_num_threads(num_threads, heap_size),
+                    Some(num_threads),
+                ),
+            };
+            let writer = writer.map_err(to_pyerr)?;
             let schema = self.index.schema();
-            Ok(IndexWriter {
-                inner_index_writer: Some(writer),
+            Ok(IndexWriter::new(
+                writer,
                 schema,
-            })
+                heap_size,
+                num_threads,
+                auto_commit,
+                auto_commit_payload,
+            ))
         })
     }
 
@@ -509,6 +1551,346 @@ impl Index {
         })
     }
 
+    /// Verify the integrity of the index stored at `path`, without opening
+    /// it for searching.
+    ///
+    /// See `verify()` for the checks that are run. A `meta.json` file that
+    /// cannot be parsed is reported as damaged.
+    ///
+    /// Args:
+    ///     path (str): The directory containing the index.
+    ///
+    /// Returns:
+    ///     VerificationReport: The missing and damaged files of the index.
+    ///
+    /// Raises an error if no index could be found at the given path or if it
+    /// could not be read for any other reason.
+    #[staticmethod]
+    fn validate(py: Python, path: &str) -> PyResult<VerificationReport> {
+        py.detach(move || match tv::Index::open_in_dir(path) {
+            Ok(index) => verify_index(&index).map_err(to_pyerr),
+            Err(tv::TantivyError::DataCorruption(_)) => {
+                Ok(VerificationReport {
+                    damaged_files: vec![META_FILEPATH.to_string()],
+                    ..VerificationReport::default()
+                })
+            }
+            Err(e) => Err(to_pyerr(e)),
+        })
+    }
+
+    /// Verify the integrity of the committed segments of the index.
+    ///
+    /// Every file of the segments listed in `meta.json` must exist, and its
+    /// content must match the checksum stored in its footer. This reads all
+    /// the files of the index, which can take a while for large indexes.
+    ///
+    /// Returns:
+    ///     VerificationReport: The missing and damaged files of the index.
+    fn verify(&self, py: Python) -> PyResult<VerificationReport> {
+        py.detach(move || verify_index(&self.index).map_err(to_pyerr))
+    }
+
+    /// Copy the commit that the index searches to `dest_path`, producing an
+    /// index that can be opened on its own.
+    ///
+    /// Only the last commit of the index can be copied: when a writer
+    /// committed since the reader was last reloaded, `reload()` must be
+    /// called first.
+    ///
+    /// The files of the commit are hard-linked to the destination when it is
+    /// on the same file system as the index, and copied otherwise. Writers
+    /// keep running while the snapshot is taken: the files are protected
+    /// from their garbage collection until they are linked or opened.
+    ///
+    /// Args:
+    ///     dest_path (str): The directory of the snapshot. It is created if
+    ///         it does not exist and must not contain an index.
+    ///
+    /// Returns:
+    ///     int: The opstamp of the commit in the snapshot.
+    ///
+    /// Raises ValueError if the reader does not search the last commit.
+    fn snapshot(&self, py: Python, dest_path: &str) -> PyResult<u64> {
+        py.detach(move || {
+            let searcher = self.reader.searcher();
+            self.write_commit(Path::new(dest_path), Some(searcher.generation()))
+        })
+    }
+
+    /// Write the last commit of the index to the directory at `path`.
+    ///
+    /// This is mostly useful to persist an index created in memory, see
+    /// `snapshot()` for the details.
+    ///
+    /// Args:
+    ///     path (str): The directory where the index is written. It is
+    ///         created if it does not exist and must not contain an index.
+    fn save(&self, py: Python, path: &str) -> PyResult<()> {
+        py.detach(move || {
+            self.write_commit(Path::new(path), None).map(|_opstamp| ())
+        })
+    }
+
+    /// Pack the last commit of the index into a single bytes object.
+    ///
+    /// The index can be recreated in memory with `Index.from_bytes()`, in
+    /// this process or another one.
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        let archive = py.detach(move || {
+            let commit = self.read_last_commit(None, |_path| false)?;
+            let mut archive = ARCHIVE_MAGIC.to_vec();
+            commit.write_to(|path, bytes| {
+                let name = path.to_string_lossy();
+                archive.extend((name.len() as u32).to_le_bytes());
+                archive.extend(name.as_bytes());
+                archive.extend((bytes.len() as u64).to_le_bytes());
+                archive.extend(bytes);
+                Ok(())
+            })?;
+            Ok::<_, PyErr>(archive)
+        })?;
+        Ok(PyBytes::new(py, &archive))
+    }
+
+    /// Open an in-memory index from the bytes returned by `to_bytes()`.
+    ///
+    /// Args:
+    ///     data (bytes): The packed index.
+    ///
+    /// Raises ValueError if the data is not a packed index.
+    #[staticmethod]
+    fn from_bytes(py: Python, data: &[u8]) -> PyResult<Index> {
+        py.detach(move || {
+            let files = unpack_archive(data).ok_or_else(|| {
+                exceptions::PyValueError::new_err(
+                    "The data is not a packed index.",
+                )
+            })?;
+            let directory: Box<dyn Directory> =
+                Box::new(RamDirectory::create());
+            for (path, bytes) in files {
+                directory
+                    .atomic_write(Path::new(path), bytes)
+                    .map_err(to_pyerr)?;
+            }
+            let index =
+                tv::Index::open(directory.box_clone()).map_err(to_pyerr)?;
+
+            Index::register_custom_text_analyzers(&index);
+            Index::register_persisted_analyzers(&index)?;
+
+            let reader = index.reader().map_err(to_pyerr)?;
+            Ok(Index {
+                index,
+                reader,
+                directory,
+                path: None,
+                read_only: false,
+                preloaded_bytes: BTreeMap::new(),
+                read_cache_size: None,
+            })
+        })
+    }
+
+    /// Merge the last commits of several indexes into a new index, as a
+    /// single segment.
+    ///
+    /// The documents are copied from the segments of the indexes, without
+    /// reading their source data again. The analyzers persisted in the
+    /// indexes are kept. The commits made by writers of the indexes while
+    /// they are merged are left out, and the garbage collection of their
+    /// files is held off until the merge completes.
+    ///
+    /// Args:
+    ///     indexes (List[Index]): The indexes to merge. They must have the
+    ///         same schema and settings.
+    ///     output_path (str): The directory of the new index. It is created
+    ///         if it does not exist and must not contain an index.
+    ///     filter_query (Query, optional): The documents matching this query
+    ///         are left out of the new index.
+    ///
+    /// Returns:
+    ///     Index: The new index.
+    #[staticmethod]
+    #[pyo3(signature = (indexes, output_path, filter_query = None))]
+    fn merge_from(
+        py: Python,
+        indexes: Vec<PyRef<Index>>,
+        output_path: &str,
+        filter_query: Option<&Query>,
+    ) -> PyResult<Index> {
+        let Some(first) = indexes.first() else {
+            return Err(exceptions::PyValueError::new_err(
+                "At least one index must be given.",
+            ));
+        };
+        let settings = first.index.settings().clone();
+        if indexes
+            .iter()
+            .any(|index| index.index.settings() != &settings)
+        {
+            return Err(exceptions::PyValueError::new_err(
+                "All the indexes must have the same settings.",
+            ));
+        }
+        // The paths are compared once resolved, since locking the same
+        // directory twice would never return.
+        let paths = indexes
+            .iter()
+            .map(|index| {
+                index.path.as_ref().map(std::fs::canonicalize).transpose()
+            })
+            .collect::<std::io::Result<Vec<_>>>()
+            .map_err(to_pyerr)?;
+        for (i, index) in indexes.iter().enumerate() {
+            if (0..i).any(|j| {
+                std::ptr::eq(&**index, &*indexes[j])
+                    || paths[i].is_some() && paths[i] == paths[j]
+            }) {
+                return Err(exceptions::PyValueError::new_err(
+                    "The same index cannot be merged twice.",
+                ));
+            }
+        }
+        let sources: Vec<tv::Index> =
+            indexes.iter().map(|index| index.index.clone()).collect();
+        py.detach(move || {
+            let output_dir = Path::new(output_path);
+            if output_dir.join(META_FILEPATH).exists() {
+                return Err(exceptions::PyValueError::new_err(format!(
+                    "`{}` already contains an index.",
+                    output_dir.display()
+                )));
+            }
+            std::fs::create_dir_all(output_dir).map_err(to_pyerr)?;
+
+            // Holding the meta locks keeps the garbage collection of the
+            // writers away from the segments being merged.
+            let mut meta_locks = Vec::new();
+            let mut segments = Vec::new();
+            let mut analyzers = PersistedAnalyzers::default();
+            for source in &sources {
+                meta_locks.push(
+                    source
+                        .directory()
+                        .acquire_lock(&META_LOCK)
+                        .map_err(to_pyerr)?,
+                );
+                segments
+                    .extend(source.searchable_segments().map_err(to_pyerr)?);
+                let persisted = Index::read_persisted_analyzers(source)?;
+                for (name, definition) in persisted.tokenizers {
+                    analyzers.tokenizers.entry(name).or_insert(definition);
+                }
+                for (name, definition) in persisted.fast_field_tokenizers {
+                    analyzers
+                        .fast_field_tokenizers
+                        .entry(name)
+                        .or_insert(definition);
+                }
+            }
+            let alive_bitsets = match filter_query {
+                Some(query) => {
+                    let schema = sources[0].schema();
+                    let weight = query
+                        .get()
+                        .weight(EnableScoring::disabled_from_schema(&schema))
+                        .map_err(to_pyerr)?;
+                    segments
+                        .iter()
+                        .map(|segment| {
+                            unmatched_docs(segment, weight.as_ref()).map(Some)
+                        })
+                        .collect::<tv::Result<Vec<_>>>()
+                        .map_err(to_pyerr)?
+                }
+                None => segments.iter().map(|_| None).collect(),
+            };
+
+            let directory = Index::open_directory(Some(output_path), None, 0)?;
+            if !analyzers.tokenizers.is_empty()
+                || !analyzers.fast_field_tokenizers.is_empty()
+            {
+                let bytes =
+                    serde_json::to_vec_pretty(&analyzers).map_err(to_pyerr)?;
+                directory
+                    .atomic_write(Path::new(ANALYZERS_FILEPATH), &bytes)
+                    .map_err(to_pyerr)?;
+            }
+            let index = if segments.is_empty() {
+                tv::Index::create(
+                    directory.box_clone(),
+                    sources[0].schema(),
+                    settings,
+                )
+            } else {
+                tv::indexer::merge_filtered_segments(
+                    &segments,
+                    settings,
+                    alive_bitsets,
+                    directory.box_clone(),
+                )
+            }
+            .map_err(to_pyerr)?;
+
+            Index::register_custom_text_analyzers(&index);
+            Index::register_persisted_analyzers(&index)?;
+
+            let reader = index.reader().map_err(to_pyerr)?;
+            Ok(Index {
+                index,
+                reader,
+                directory,
+                path: Some(output_dir.to_path_buf()),
+                read_only: false,
+                preloaded_bytes: BTreeMap::new(),
+                read_cache_size: None,
+            })
+        })
+    }
+
+    /// Load components of the segments of the index, so that the searches
+    /// using them do not wait for the disk.
+    ///
+    /// The files of an index on disk are read into the page cache of the
+    /// operating system. The files of a `directory` are read into its read
+    /// cache, which must be large enough to hold them.
+    ///
+    /// Args:
+    ///     components (List[str]): The components to load, among "postings",
+    ///         "positions", "terms", "store", "fast" and "fieldnorms".
+    ///
+    /// Returns:
+    ///     Dict[str, int]: The number of bytes loaded for every component.
+    ///
+    /// Raises ValueError if the read cache of the `directory` of the index
+    /// is too small for the components.
+    fn preload(
+        &self,
+        py: Python,
+        components: Vec<String>,
+    ) -> PyResult<BTreeMap<&'static str, u64>> {
+        let components = components
+            .iter()
+            .map(|name| preload_component(name))
+            .collect::<PyResult<Vec<_>>>()?;
+        py.detach(move || self.preload_components(&components))
+    }
+
+    /// The number of bytes of every component loaded by the `preload`
+    /// argument of `Index.open()`.
+    #[getter]
+    fn preloaded_bytes(&self) -> BTreeMap<&'static str, u64> {
+        self.preloaded_bytes.clone()
+    }
+
+    /// Whether the index was opened read-only.
+    #[getter]
+    fn read_only(&self) -> bool {
+        self.read_only
+    }
+
     /// The schema of the current index.
     #[getter]
     fn schema(&self, py: Python) -> Schema {
@@ -518,6 +1900,85 @@ impl Index {
         })
     }
 
+    /// Add new fields to the schema of an existing index.
+    ///
+    /// The fields are appended to the schema stored in the index metadata, so
+    /// documents added from now on can have them. Documents indexed before
+    /// the change simply lack these fields: they don't match queries on them
+    /// and `Searcher.fast_field_values()` reports them as missing.
+    ///
+    /// Field norms are not recorded for the new fields, since the existing
+    /// segments have none: BM25 scores on them do not take the length of
+    /// the field into account.
+    ///
+    /// The index is then reopened with the extended schema, so `schema`,
+    /// new writers and new searchers see the fields right away. The reader is
+    /// recreated with the default configuration. Other processes have to
+    /// open the index again to see the new fields.
+    ///
+    /// Args:
+    ///     fields (SchemaBuilder): A builder holding only the fields to add.
+    ///         Like with `SchemaBuilder.build()`, the builder cannot be used
+    ///         anymore afterwards.
+    ///
+    /// Raises ValueError if a field is already defined in the schema, or if
+    /// an `IndexWriter` is currently open on the index.
+    fn add_fields(
+        &mut self,
+        py: Python,
+        fields: &mut SchemaBuilder,
+    ) -> PyResult<()> {
+        self.check_writable()?;
+        let fragment = fields.build()?.inner;
+        py.detach(move || {
+            // Holding the writer lock guarantees that no commit overwrites
+            // the metadata while we update it.
+            let _lock = self
+                .index
+                .directory()
+                .acquire_lock(&INDEX_WRITER_LOCK)
+                .map_err(|_| {
+                    exceptions::PyValueError::new_err(
+                        "Cannot add fields while an IndexWriter is open on the index.",
+                    )
+                })?;
+
+            let mut metas = self.index.load_metas().map_err(to_pyerr)?;
+            let mut builder = tv::schema::Schema::builder();
+            for (_, field_entry) in metas.schema.fields() {
+                builder.add_field(field_entry.clone());
+            }
+            for (_, field_entry) in fragment.fields() {
+                if metas.schema.get_field(field_entry.name()).is_ok() {
+                    return Err(exceptions::PyValueError::new_err(format!(
+                        "Field `{}` is already defined in the schema.",
+                        field_entry.name()
+                    )));
+                }
+                builder.add_field(without_fieldnorms(field_entry)?);
+            }
+            metas.schema = builder.build();
+
+            let mut buffer =
+                serde_json::to_vec_pretty(&metas).map_err(to_pyerr)?;
+            buffer.push(b'\n');
+            self.index
+                .directory()
+                .atomic_write(Path::new(META_FILEPATH), &buffer)
+                .map_err(to_pyerr)?;
+
+            let mut index =
+                tv::Index::open(self.directory.box_clone()).map_err(to_pyerr)?;
+            index.set_tokenizers(self.index.tokenizers().clone());
+            index.set_fast_field_tokenizers(
+                self.index.fast_field_tokenizer().clone(),
+            );
+            self.reader = index.reader().map_err(to_pyerr)?;
+            self.index = index;
+            Ok(())
+        })
+    }
+
     /// Update searchers so that they reflect the state of the last .commit().
     ///
     /// If you set up the the reload policy to be on 'commit' (which is the
@@ -570,9 +2031,7 @@ impl Index {
                 allow_regexes,
             )?;
 
-            let query = parser.parse_query(query).map_err(to_pyerr)?;
-
-            Ok(Query { inner: query })
+            parser.parse_query(query)
         })
     }
 
@@ -639,45 +2098,400 @@ impl Index {
             // on the outside of the turbofish type signature.
             .collect::<PyResult<_>>()?;
 
-        Ok((Query { inner: query }, errors))
+        Ok((query, errors))
+    }
+
+    /// Returns the text analyzer used to index the given field.
+    ///
+    /// This resolves the tokenizer name declared in the schema for the field
+    /// against the analyzers registered on this index, so the returned
+    /// analyzer processes text exactly like the indexing pipeline does.
+    ///
+    /// Args:
+    ///     field_name (str): The name of a text or JSON field.
+    ///
+    /// Raises ValueError if the field does not exist, is not indexed as text,
+    /// or references a tokenizer that was not registered.
+    fn tokenizer_for_field(
+        &self,
+        field_name: &str,
+    ) -> PyResult<PyTextAnalyzer> {
+        let field = get_field(&self.index.schema(), field_name)?;
+        let analyzer =
+            self.index.tokenizer_for_field(field).map_err(to_pyerr)?;
+        Ok(PyTextAnalyzer {
+            analyzer,
+            definition: None,
+        })
     }
 
     /// Register a custom text analyzer by name. (Confusingly,
     /// this is one of the places where Tantivy uses 'tokenizer' to refer to a
     /// TextAnalyzer instance.)
     ///
+    /// Args:
+    ///     name (str): The name fields refer to with `tokenizer_name`.
+    ///     analyzer (TextAnalyzer): The analyzer to register.
+    ///     persist (bool, optional): Whether to store the analyzer definition
+    ///         alongside the index, so that `Index.open()` registers it again.
+    ///         By default, the analyzer is persisted whenever it was built with
+    ///         a `TextAnalyzerBuilder`. Passing True raises ValueError for an
+    ///         analyzer that cannot be serialized, or for an index opened
+    ///         read-only, for which analyzers are not persisted by default.
+    ///
     // Implementation notes: Skipped indirection of TokenizerManager.
+    #[pyo3(signature = (name, analyzer, persist = None))]
     pub fn register_tokenizer(
         &self,
         py: Python,
         name: &str,
         analyzer: PyTextAnalyzer,
-    ) {
+        persist: Option<bool>,
+    ) -> PyResult<()> {
+        let definition = self.definition_to_persist(&analyzer, persist)?;
         py.detach(move || {
+            if let Some(definition) = definition {
+                self.persist_analyzer(|persisted| {
+                    persisted.tokenizers.insert(name.to_string(), definition);
+                })?;
+            }
             self.index.tokenizers().register(name, analyzer.analyzer);
-        });
+            Ok(())
+        })
     }
 
     /// Register a custom text analyzer for fast fields by name. (Confusingly,
     /// this is one of the places where Tantivy uses 'tokenizer' to refer to a
     /// TextAnalyzer instance.)
     ///
+    /// See `register_tokenizer()` for the meaning of `persist`.
+    ///
     // Implementation notes: Skipped indirection of TokenizerManager.
+    #[pyo3(signature = (name, analyzer, persist = None))]
     pub fn register_fast_field_tokenizer(
         &self,
         py: Python,
         name: &str,
         analyzer: PyTextAnalyzer,
-    ) {
+        persist: Option<bool>,
+    ) -> PyResult<()> {
+        let definition = self.definition_to_persist(&analyzer, persist)?;
         py.detach(move || {
+            if let Some(definition) = definition {
+                self.persist_analyzer(|persisted| {
+                    persisted
+                        .fast_field_tokenizers
+                        .insert(name.to_string(), definition);
+                })?;
+            }
             self.index
                 .fast_field_tokenizer()
                 .register(name, analyzer.analyzer);
-        });
+            Ok(())
+        })
+    }
+}
+
+/// Warns that the persisted analyzers, or the one named `name`, are not
+/// registered because of `err`.
+fn warn_skipped_analyzers(name: Option<&str>, err: PyErr) -> PyResult<()> {
+    Python::attach(|py| {
+        let skipped = match name {
+            Some(name) => format!("the analyzer `{name}`"),
+            None => "the analyzers".to_string(),
+        };
+        let message = CString::new(format!(
+            "Skipping {skipped} persisted in `{ANALYZERS_FILEPATH}`: {}",
+            err.value(py)
+        ))?;
+        PyErr::warn(
+            py,
+            &py.get_type::<exceptions::PyRuntimeWarning>(),
+            &message,
+            1,
+        )
+    })
+}
+
+/// Parses JSON lines into documents, splitting the work between the
+/// available cores. The results keep the order of the lines.
+fn parse_json_lines(
+    schema: &tv::schema::Schema,
+    lines: &[(u64, Vec<u8>)],
+) -> Vec<(u64, Result<TantivyDocument, String>)> {
+    let num_threads =
+        std::thread::available_parallelism().map_or(1, |num| num.get());
+    let chunk_size = lines.len().div_ceil(num_threads).max(1);
+    std::thread::scope(|scope| {
+        let handles: Vec<_> = lines
+            .chunks(chunk_size)
+            .map(|chunk| {
+                scope.spawn(move || {
+                    chunk
+                        .iter()
+                        .map(|(line_number, line)| {
+                            (*line_number, parse_json_line(schema, line))
+                        })
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| {
+                handle
+                    .join()
+                    .unwrap_or_else(|err| std::panic::resume_unwind(err))
+            })
+            .collect()
+    })
+}
+
+fn parse_json_line(
+    schema: &tv::schema::Schema,
+    line: &[u8],
+) -> Result<TantivyDocument, String> {
+    let line = std::str::from_utf8(line)
+        .map_err(|err| format!("Invalid UTF-8: {err}"))?;
+    TantivyDocument::parse_json(schema, line).map_err(|err| err.to_string())
+}
+
+/// Disables field norms on a field added to an existing index.
+///
+/// Older segments have no field norms for the field, and tantivy requires
+/// them in every segment to merge segments together. Only text and JSON
+/// fields can opt out of field norms; other types are rejected.
+fn without_fieldnorms(
+    field_entry: &tv::schema::FieldEntry,
+) -> PyResult<tv::schema::FieldEntry> {
+    use tv::schema::FieldType;
+
+    let field_type = match field_entry.field_type() {
+        FieldType::Str(options) => match options.get_indexing_options() {
+            Some(indexing) => FieldType::Str(options.clone().set_indexing_options(
+                indexing.clone().set_fieldnorms(false),
+            )),
+            None => FieldType::Str(options.clone()),
+        },
+        FieldType::JsonObject(options) => {
+            match options.get_text_indexing_options() {
+                Some(indexing) => FieldType::JsonObject(
+                    options.clone().set_indexing_options(
+                        indexing.clone().set_fieldnorms(false),
+                    ),
+                ),
+                None => FieldType::JsonObject(options.clone()),
+            }
+        }
+        field_type if field_type.has_fieldnorms() => {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "Field `{}` records field norms, which is not supported for fields added to an existing index.",
+                field_entry.name()
+            )))
+        }
+        field_type => field_type.clone(),
+    };
+    Ok(tv::schema::FieldEntry::new(
+        field_entry.name().to_string(),
+        field_type,
+    ))
+}
+
+/// Returns the definition to persist for `analyzer`, given the `persist`
+/// argument of the `register_*tokenizer` methods.
+fn persisted_definition(
+    analyzer: &PyTextAnalyzer,
+    persist: Option<bool>,
+) -> PyResult<Option<AnalyzerDefinition>> {
+    match (persist, &analyzer.definition) {
+        (Some(false), _) => Ok(None),
+        (_, Some(definition)) => Ok(Some(definition.clone())),
+        (None, None) => Ok(None),
+        (Some(true), None) => Err(exceptions::PyValueError::new_err(
+            "This TextAnalyzer was not built with a TextAnalyzerBuilder and cannot be persisted.",
+        )),
     }
 }
 
 impl Index {
+    fn check_writable(&self) -> PyResult<()> {
+        if self.read_only {
+            return Err(exceptions::PyValueError::new_err(
+                "The index was opened read-only.",
+            ));
+        }
+        Ok(())
+    }
+
+    /// The definition of an analyzer registered with `persist` to store,
+    /// nothing is stored for a read-only index unless `persist` is True.
+    fn definition_to_persist(
+        &self,
+        text_analyzer: &PyTextAnalyzer,
+        persist: Option<bool>,
+    ) -> PyResult<Option<AnalyzerDefinition>> {
+        if !self.read_only {
+            persisted_definition(text_analyzer, persist)
+        } else if persist == Some(true) {
+            self.check_writable().map(|_| None)
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Reads the given components of the searchable segments.
+    fn preload_components(
+        &self,
+        components: &[(&'static str, SegmentComponent)],
+    ) -> PyResult<BTreeMap<&'static str, u64>> {
+        let segment_metas =
+            self.index.searchable_segment_metas().map_err(to_pyerr)?;
+        let mut files = Vec::new();
+        for &(name, component) in components {
+            for segment_meta in &segment_metas {
+                let file = self
+                    .index
+                    .directory()
+                    .open_read(&segment_meta.relative_path(component))
+                    .map_err(to_pyerr)?;
+                files.push((name, file));
+            }
+        }
+        // Only what the read cache holds stays loaded.
+        let total_bytes: usize = files.iter().map(|(_, file)| file.len()).sum();
+        if let Some(read_cache_size) = self.read_cache_size {
+            if total_bytes > read_cache_size {
+                return Err(exceptions::PyValueError::new_err(format!(
+                    "The read cache of {read_cache_size} bytes cannot hold \
+                     the {total_bytes} bytes of the components to preload, \
+                     increase `read_cache_size`."
+                )));
+            }
+        }
+
+        let mut preloaded_bytes: BTreeMap<_, u64> =
+            components.iter().map(|&(name, _)| (name, 0)).collect();
+        for (name, file) in files {
+            let bytes = file.read_bytes().map_err(to_pyerr)?;
+            // Reading a byte of every page of a memory-mapped file loads
+            // it into the page cache.
+            let touched = bytes
+                .as_slice()
+                .iter()
+                .step_by(4096)
+                .fold(0u8, |acc, byte| acc.wrapping_add(*byte));
+            std::hint::black_box(touched);
+            *preloaded_bytes.entry(name).or_default() += bytes.len() as u64;
+        }
+        Ok(preloaded_bytes)
+    }
+
+    /// The directory of an index stored at `path`, through the Python
+    /// `storage` object, or in memory if none of them is given.
+    fn open_directory(
+        path: Option<&str>,
+        storage: Option<Py<PyAny>>,
+        read_cache_size: usize,
+    ) -> PyResult<Box<dyn Directory>> {
+        Ok(match (path, storage) {
+            (Some(_), Some(_)) => {
+                return Err(exceptions::PyValueError::new_err(
+                    "Only one of `path` and `directory` can be given.",
+                ))
+            }
+            (Some(path), None) => {
+                Box::new(MmapDirectory::open(path).map_err(to_pyerr)?)
+            }
+            (None, Some(storage)) => {
+                Box::new(PyDirectory::new(storage, read_cache_size))
+            }
+            (None, None) => Box::new(RamDirectory::create()),
+        })
+    }
+
+    /// Copies the last commit of the index to `dest_path`, returning its
+    /// opstamp. If a `generation` is given, the commit must be the one it
+    /// searches.
+    fn write_commit(
+        &self,
+        dest_path: &Path,
+        generation: Option<&tv::SearcherGeneration>,
+    ) -> PyResult<u64> {
+        if dest_path.join(META_FILEPATH).exists() {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "`{}` already contains an index.",
+                dest_path.display()
+            )));
+        }
+        std::fs::create_dir_all(dest_path).map_err(to_pyerr)?;
+
+        let commit = self.read_last_commit(generation, |path| {
+            self.path.as_ref().is_some_and(|source| {
+                std::fs::hard_link(source.join(path), dest_path.join(path))
+                    .is_ok()
+            })
+        })?;
+        let opstamp = commit.opstamp;
+        commit.write_to(|path, bytes| {
+            std::fs::write(dest_path.join(path), bytes).map_err(to_pyerr)
+        })?;
+        Ok(opstamp)
+    }
+
+    /// Reads the last commit of the index, calling `link` for each of its
+    /// files while they are protected from the garbage collection of the
+    /// writers. The files for which `link` returns false are opened.
+    ///
+    /// If a `generation` is given, the commit must be the one it searches.
+    fn read_last_commit(
+        &self,
+        generation: Option<&tv::SearcherGeneration>,
+        link: impl Fn(&Path) -> bool,
+    ) -> PyResult<LastCommit> {
+        // The garbage collection takes the meta lock to list the files it
+        // must keep.
+        let _meta_lock = self
+            .index
+            .directory()
+            .acquire_lock(&META_LOCK)
+            .map_err(to_pyerr)?;
+        let meta = self
+            .directory
+            .atomic_read(Path::new(META_FILEPATH))
+            .map_err(to_pyerr)?;
+        let commit: CommitMeta =
+            serde_json::from_slice(&meta).map_err(to_pyerr)?;
+        if generation
+            .is_some_and(|generation| !commit.is_searched_by(generation))
+        {
+            return Err(exceptions::PyValueError::new_err(
+                "The index reader does not search the last commit yet, call \
+                 `reload()` first.",
+            ));
+        }
+        let files = commit.files(&self.index).map_err(to_pyerr)?;
+        let mut opened_files = Vec::new();
+        for path in &files {
+            if !link(path) {
+                let file = self.directory.open_read(path).map_err(to_pyerr)?;
+                opened_files.push((path.clone(), file));
+            }
+        }
+        let analyzers =
+            match self.directory.atomic_read(Path::new(ANALYZERS_FILEPATH)) {
+                Ok(bytes) => Some(bytes),
+                Err(OpenReadError::FileDoesNotExist(_)) => None,
+                Err(err) => return Err(to_pyerr(err)),
+            };
+        Ok(LastCommit {
+            opstamp: commit.opstamp,
+            meta,
+            files,
+            opened_files,
+            analyzers,
+        })
+    }
+
     fn prepare_query_parser(
         &self,
         default_field_names: Option<Vec<String>>,
@@ -685,7 +2499,7 @@ impl Index {
         fuzzy_fields: HashMap<String, (bool, u8, bool)>,
         conjunction_by_default: bool,
         allow_regexes: bool,
-    ) -> PyResult<tv::query::QueryParser> {
+    ) -> PyResult<QueryParser> {
         let schema = self.index.schema();
 
         let default_fields = if let Some(default_field_names) =
@@ -726,6 +2540,7 @@ impl Index {
             parser.allow_regexes();
         }
 
+        let mut boosted_fields = Vec::with_capacity(field_boosts.len());
         for (field_name, boost) in field_boosts {
             let field = schema.get_field(&field_name).map_err(|_err| {
                 exceptions::PyValueError::new_err(format!(
@@ -733,6 +2548,7 @@ impl Index {
                 ))
             })?;
             parser.set_field_boost(field, boost);
+            boosted_fields.push((field, boost));
         }
 
         for (field_name, (prefix, distance, transpose_cost_one)) in fuzzy_fields
@@ -745,10 +2561,15 @@ impl Index {
             parser.set_field_fuzzy(field, prefix, distance, transpose_cost_one);
         }
 
-        Ok(parser)
+        Ok(QueryParser::new(
+            parser,
+            schema,
+            boosted_fields,
+            conjunction_by_default,
+        ))
     }
 
-    fn register_custom_text_analyzers(index: &tv::Index) {
+    pub(crate) fn register_custom_text_analyzers(index: &tv::Index) {
         let analyzers = [
             ("ar_stem", Language::Arabic),
             ("da_stem", Language::Danish),
@@ -778,4 +2599,65 @@ impl Index {
             index.tokenizers().register(name, an);
         }
     }
+
+    fn read_persisted_analyzers(
+        index: &tv::Index,
+    ) -> PyResult<PersistedAnalyzers> {
+        match index.directory().atomic_read(Path::new(ANALYZERS_FILEPATH)) {
+            Ok(bytes) => serde_json::from_slice(&bytes).map_err(|err| {
+                exceptions::PyValueError::new_err(format!(
+                    "Invalid analyzer definitions in `{ANALYZERS_FILEPATH}`: {err}"
+                ))
+            }),
+            Err(OpenReadError::FileDoesNotExist(_)) => {
+                Ok(PersistedAnalyzers::default())
+            }
+            Err(err) => Err(to_pyerr(err)),
+        }
+    }
+
+    /// Registers the analyzers persisted by `register_tokenizer()` and
+    /// `register_fast_field_tokenizer()`.
+    ///
+    /// The analyzers that cannot be read are skipped with a RuntimeWarning,
+    /// so that a damaged sidecar file cannot make the index unopenable.
+    fn register_persisted_analyzers(index: &tv::Index) -> PyResult<()> {
+        let persisted = match Index::read_persisted_analyzers(index) {
+            Ok(persisted) => persisted,
+            Err(err) => return warn_skipped_analyzers(None, err),
+        };
+        for (name, definition) in &persisted.tokenizers {
+            match definition.build() {
+                Ok(analyzer) => index.tokenizers().register(name, analyzer),
+                Err(err) => warn_skipped_analyzers(Some(name), err)?,
+            }
+        }
+        for (name, definition) in &persisted.fast_field_tokenizers {
+            match definition.build() {
+                Ok(analyzer) => {
+                    index.fast_field_tokenizer().register(name, analyzer)
+                }
+                Err(err) => warn_skipped_analyzers(Some(name), err)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn persist_analyzer(
+        &self,
+        update: impl FnOnce(&mut PersistedAnalyzers),
+    ) -> PyResult<()> {
+        let _lock = self
+            .index
+            .directory()
+            .acquire_lock(&analyzers_lock())
+            .map_err(to_pyerr)?;
+        let mut persisted = Index::read_persisted_analyzers(&self.index)?;
+        update(&mut persisted);
+        let bytes = serde_json::to_vec_pretty(&persisted).map_err(to_pyerr)?;
+        self.index
+            .directory()
+            .atomic_write(Path::new(ANALYZERS_FILEPATH), &bytes)
+            .map_err(to_pyerr)
+    }
 }
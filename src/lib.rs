@@ -2,30 +2,45 @@ use ::tantivy as tv;
 use ::tantivy::schema::{OwnedValue as Value, Term};
 use pyo3::{exceptions, prelude::*, wrap_pymodule};
 
+mod directory;
 mod document;
+mod error;
 mod explanation;
 mod facet;
 mod index;
 mod parser_error;
+mod percolator;
 mod query;
 mod query_grammar;
+mod query_parser;
+mod query_spec;
 mod schema;
 mod schemabuilder;
 mod searcher;
 mod snippet;
+mod token_filters;
 mod tokenizer;
 
 use document::{extract_value_for_type, Document};
+use error::{
+    DataCorruption, IncompatibleIndexError, IndexCorruptedError, IoError,
+    LockBusyError, SchemaError, TantivyError,
+};
 use explanation::Explanation;
 use facet::Facet;
-use index::{Index, IndexWriter};
+use index::{
+    Index, IndexWriter, JsonFileReport, VerificationReport, WriterStats,
+};
+use percolator::Percolator;
 use query::{Occur, Query};
 use query_grammar::{parse_query, parse_query_lenient};
-use schema::{FieldType, Schema};
+use schema::{FieldEntry, FieldType, Schema, ValidationProblem};
 use schemabuilder::SchemaBuilder;
-use searcher::{DocAddress, Order, SearchResult, Searcher};
+use searcher::{
+    DocAddress, MultiSearchResult, MultiSearcher, Order, SearchResult, Searcher,
+};
 use snippet::{Snippet, SnippetGenerator};
-use tokenizer::{Filter, TextAnalyzer, TextAnalyzerBuilder, Tokenizer};
+use tokenizer::{Filter, TextAnalyzer, TextAnalyzerBuilder, Token, Tokenizer};
 
 /// Python bindings for the search engine library Tantivy.
 ///
@@ -74,9 +89,15 @@ fn tantivy(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<SchemaBuilder>()?;
     m.add_class::<Searcher>()?;
     m.add_class::<SearchResult>()?;
+    m.add_class::<MultiSearcher>()?;
+    m.add_class::<MultiSearchResult>()?;
+    m.add_class::<Percolator>()?;
     m.add_class::<Document>()?;
     m.add_class::<Index>()?;
     m.add_class::<IndexWriter>()?;
+    m.add_class::<JsonFileReport>()?;
+    m.add_class::<WriterStats>()?;
+    m.add_class::<VerificationReport>()?;
     m.add_class::<DocAddress>()?;
     m.add_class::<Facet>()?;
     m.add_class::<Query>()?;
@@ -85,10 +106,21 @@ fn tantivy(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     m.add_class::<SnippetGenerator>()?;
     m.add_class::<Occur>()?;
     m.add_class::<FieldType>()?;
+    m.add_class::<FieldEntry>()?;
+    m.add_class::<ValidationProblem>()?;
     m.add_class::<Tokenizer>()?;
     m.add_class::<TextAnalyzerBuilder>()?;
     m.add_class::<Filter>()?;
     m.add_class::<TextAnalyzer>()?;
+    m.add_class::<Token>()?;
+
+    m.add_class::<TantivyError>()?;
+    m.add_class::<LockBusyError>()?;
+    m.add_class::<IndexCorruptedError>()?;
+    m.add_class::<DataCorruption>()?;
+    m.add_class::<IncompatibleIndexError>()?;
+    m.add_class::<SchemaError>()?;
+    m.add_class::<IoError>()?;
 
     m.add_function(wrap_pyfunction!(parse_query, m)?)?;
     m.add_function(wrap_pyfunction!(parse_query_lenient, m)?)?;
@@ -147,8 +179,8 @@ fn query_parser_error(_py: Python, m: &Bound<PyModule>) -> PyResult<()> {
     Ok(())
 }
 
-pub(crate) fn to_pyerr<E: ToString>(err: E) -> PyErr {
-    exceptions::PyValueError::new_err(err.to_string())
+pub(crate) fn to_pyerr<E: ToString + 'static>(err: E) -> PyErr {
+    error::to_pyerr(&err, err.to_string())
 }
 
 pub(crate) fn get_field(
@@ -0,0 +1,306 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    sync::RwLock,
+};
+
+use pyo3::{exceptions::PyValueError, prelude::*};
+use tantivy as tv;
+use tantivy::{
+    collector::Count,
+    query::{BooleanQuery, Occur, PhraseQuery, TermQuery},
+    schema::{Field, NamedFieldDocument, Term},
+    tokenizer::TokenizerManager,
+    ReloadPolicy, SingleSegmentIndexWriter, TantivyDocument,
+};
+
+use crate::{
+    document::Document,
+    index::Index,
+    query::Query,
+    schema::{check_values_for_type, Schema},
+    to_pyerr,
+    tokenizer::TextAnalyzer,
+};
+
+/// Memory budget of the writer indexing the matched document, which is
+/// enough for a single document.
+const DOCUMENT_MEMORY_BUDGET: usize = 1_000_000;
+
+/// A term as found in the term dictionary of its field.
+type TermKey = (Field, Vec<u8>);
+
+fn term_key(term: &Term) -> TermKey {
+    (term.field(), term.serialized_value_bytes().to_vec())
+}
+
+/// Returns terms of which every document matching `query` contains at least
+/// one, or None if the query may match documents without any of its terms.
+fn required_terms(query: &dyn tv::query::Query) -> Option<Vec<Term>> {
+    if let Some(query) = query.downcast_ref::<TermQuery>() {
+        return Some(vec![query.term().clone()]);
+    }
+    if let Some(query) = query.downcast_ref::<PhraseQuery>() {
+        return Some(query.phrase_terms());
+    }
+    let query = query.downcast_ref::<BooleanQuery>()?;
+    let clauses = query.clauses();
+    if clauses.iter().any(|(occur, _)| *occur == Occur::Must) {
+        return clauses
+            .iter()
+            .filter(|(occur, _)| *occur == Occur::Must)
+            .find_map(|(_, query)| required_terms(query.as_ref()));
+    }
+    // Without mandatory clauses, the documents match one of the optional
+    // ones.
+    let mut terms = Vec::new();
+    for (occur, query) in clauses {
+        if *occur == Occur::Should {
+            terms.extend(required_terms(query.as_ref())?);
+        }
+    }
+    (!terms.is_empty()).then_some(terms)
+}
+
+/// Checks that the values of `doc` have the type of their field, as the
+/// writer panics on the others, e.g. a signed integer in an unsigned field.
+fn check_document(schema: &tv::schema::Schema, doc: &Document) -> PyResult<()> {
+    for (field_name, values) in doc.field_values.iter() {
+        let field = schema.get_field(field_name).map_err(|_| {
+            PyValueError::new_err(format!(
+                "Field `{field_name}` is not defined in the schema."
+            ))
+        })?;
+        let field_type = schema.get_field_entry(field).field_type();
+        if let Some((_, message)) =
+            check_values_for_type(values, field_type, field_name)
+                .into_iter()
+                .next()
+        {
+            return Err(PyValueError::new_err(message));
+        }
+    }
+    Ok(())
+}
+
+/// A query stored in a `Percolator`.
+struct StoredQuery {
+    query: Box<dyn tv::query::Query>,
+    /// The terms of which matching documents contain at least one, if known.
+    terms: Option<Vec<TermKey>>,
+}
+
+/// The queries of a `Percolator`, indexed by the terms they require.
+#[derive(Default)]
+struct StoredQueries {
+    queries: BTreeMap<String, StoredQuery>,
+    /// The ids of the queries that can only match documents containing a
+    /// given term.
+    queries_by_term: HashMap<TermKey, BTreeSet<String>>,
+    /// The ids of the queries evaluated against every document.
+    unfiltered_queries: BTreeSet<String>,
+}
+
+impl StoredQueries {
+    fn insert(&mut self, query_id: String, stored: StoredQuery) {
+        self.remove(&query_id);
+        match &stored.terms {
+            Some(terms) => {
+                for term in terms {
+                    self.queries_by_term
+                        .entry(term.clone())
+                        .or_default()
+                        .insert(query_id.clone());
+                }
+            }
+            None => {
+                self.unfiltered_queries.insert(query_id.clone());
+            }
+        }
+        self.queries.insert(query_id, stored);
+    }
+
+    fn remove(&mut self, query_id: &str) -> bool {
+        let Some(stored) = self.queries.remove(query_id) else {
+            return false;
+        };
+        match stored.terms {
+            Some(terms) => {
+                for term in terms {
+                    if let Some(ids) = self.queries_by_term.get_mut(&term) {
+                        ids.remove(query_id);
+                        if ids.is_empty() {
+                            self.queries_by_term.remove(&term);
+                        }
+                    }
+                }
+            }
+            None => {
+                self.unfiltered_queries.remove(query_id);
+            }
+        }
+        true
+    }
+}
+
+/// Matches documents against a set of stored queries, which is the inverse
+/// of a search: instead of finding the documents matching a query, it finds
+/// the queries matching a document.
+///
+/// Every document is indexed on its own in memory, and only evaluated
+/// against the queries that it can match given the terms it contains.
+///
+/// Args:
+///     schema (Schema): The schema of the documents, which the queries
+///         refer to.
+///
+/// A Percolator can be shared between Python threads, queries can be added
+/// and removed while documents are matched.
+#[pyclass(frozen, module = "tantivy.tantivy")]
+pub(crate) struct Percolator {
+    schema: tv::schema::Schema,
+    tokenizers: TokenizerManager,
+    fast_field_tokenizers: TokenizerManager,
+    /// The lock must be taken without holding the GIL.
+    queries: RwLock<StoredQueries>,
+}
+
+#[pymethods]
+impl Percolator {
+    #[new]
+    fn new(schema: &Schema) -> Self {
+        // The analyzers registered by default on an index.
+        let index = tv::Index::create_in_ram(schema.inner.clone());
+        Index::register_custom_text_analyzers(&index);
+        Percolator {
+            schema: schema.inner.clone(),
+            tokenizers: index.tokenizers().clone(),
+            fast_field_tokenizers: index.fast_field_tokenizer().clone(),
+            queries: RwLock::default(),
+        }
+    }
+
+    /// Store a query, replacing the query stored with the same id if any.
+    ///
+    /// Args:
+    ///     query_id (str): The id returned by `match()` when the query
+    ///         matches a document.
+    ///     query (Query): The query, which must be built for the schema of
+    ///         the percolator.
+    fn add(&self, py: Python, query_id: String, query: &Query) {
+        let stored = StoredQuery {
+            query: query.inner.box_clone(),
+            terms: required_terms(query.matching_query())
+                .map(|terms| terms.iter().map(term_key).collect()),
+        };
+        py.detach(move || {
+            self.queries.write().unwrap().insert(query_id, stored)
+        });
+    }
+
+    /// Remove a stored query.
+    ///
+    /// Args:
+    ///     query_id (str): The id of the query.
+    ///
+    /// Returns True if the query was stored.
+    fn remove(&self, py: Python, query_id: &str) -> bool {
+        py.detach(move || self.queries.write().unwrap().remove(query_id))
+    }
+
+    fn __len__(&self, py: Python) -> usize {
+        py.detach(|| self.queries.read().unwrap().queries.len())
+    }
+
+    fn __contains__(&self, py: Python, query_id: &str) -> bool {
+        py.detach(move || {
+            self.queries.read().unwrap().queries.contains_key(query_id)
+        })
+    }
+
+    /// Register a custom text analyzer by name, to analyze the text fields
+    /// of the documents as the index the queries were built for does.
+    ///
+    /// Args:
+    ///     name (str): The name fields refer to with `tokenizer_name`.
+    ///     text_analyzer (TextAnalyzer): The analyzer to register.
+    fn register_tokenizer(&self, name: &str, text_analyzer: TextAnalyzer) {
+        self.tokenizers.register(name, text_analyzer.analyzer);
+    }
+
+    /// Find the stored queries that match a document.
+    ///
+    /// Args:
+    ///     doc (Document): The document to match.
+    ///
+    /// Returns the sorted list of the ids of the matching queries.
+    ///
+    /// Raises ValueError if a field of the document is not defined in the
+    /// schema or has values of another type.
+    #[pyo3(name = "match")]
+    fn match_document(
+        &self,
+        py: Python,
+        doc: &Document,
+    ) -> PyResult<Vec<String>> {
+        check_document(&self.schema, doc)?;
+        py.detach(move || {
+            let named_doc = NamedFieldDocument(doc.field_values.clone());
+            let doc =
+                TantivyDocument::convert_named_doc(&self.schema, named_doc)
+                    .map_err(to_pyerr)?;
+
+            let mut index = tv::Index::create_in_ram(self.schema.clone());
+            index.set_tokenizers(self.tokenizers.clone());
+            index.set_fast_field_tokenizers(self.fast_field_tokenizers.clone());
+            let mut writer =
+                SingleSegmentIndexWriter::new(index, DOCUMENT_MEMORY_BUDGET)
+                    .map_err(to_pyerr)?;
+            writer.add_document(doc).map_err(to_pyerr)?;
+            let index = writer.finalize().map_err(to_pyerr)?;
+            let searcher = index
+                .reader_builder()
+                .reload_policy(ReloadPolicy::Manual)
+                .try_into()
+                .map_err(to_pyerr)?
+                .searcher();
+
+            let stored = self.queries.read().unwrap();
+            let mut candidates: BTreeSet<&str> = stored
+                .unfiltered_queries
+                .iter()
+                .map(String::as_str)
+                .collect();
+            for segment_reader in searcher.segment_readers() {
+                for (field, field_entry) in self.schema.fields() {
+                    if !field_entry.is_indexed() {
+                        continue;
+                    }
+                    let inverted_index = segment_reader
+                        .inverted_index(field)
+                        .map_err(to_pyerr)?;
+                    let mut terms =
+                        inverted_index.terms().stream().map_err(to_pyerr)?;
+                    while terms.advance() {
+                        let key = (field, terms.key().to_vec());
+                        if let Some(ids) = stored.queries_by_term.get(&key) {
+                            candidates.extend(ids.iter().map(String::as_str));
+                        }
+                    }
+                }
+            }
+
+            let mut matches = Vec::new();
+            for query_id in candidates {
+                let query = &stored.queries[query_id].query;
+                if searcher.search(query, &Count).map_err(to_pyerr)? > 0 {
+                    matches.push(query_id.to_string());
+                }
+            }
+            Ok(matches)
+        })
+    }
+
+    fn __repr__(&self, py: Python) -> String {
+        format!("Percolator(num_queries={})", self.__len__(py))
+    }
+}
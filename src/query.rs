@@ -1,19 +1,32 @@
 use crate::{
-    document::Document, explanation::Explanation, get_field, make_term,
-    make_term_for_type, schema::FieldType, searcher::Searcher, to_pyerr,
-    DocAddress, Schema,
+    document::{value_to_py, Document},
+    explanation::Explanation,
+    get_field, make_term, make_term_for_type,
+    query_spec::{
+        index_record_option, MoreLikeThisOptions, QuerySpec, RangeBound,
+        TermSpec,
+    },
+    schema::FieldType,
+    searcher::Searcher,
+    to_pyerr, DocAddress, Schema,
 };
 use core::ops::Bound as OpsBound;
 use pyo3::{
     exceptions,
     prelude::*,
-    types::{PyAny, PyDict, PyFloat, PyString},
+    types::{PyAny, PyDict, PyFloat, PyString, PyTuple},
 };
+use serde::{Deserialize, Serialize};
 use tantivy as tv;
+use tantivy::schema::{OwnedValue as Value, Term, Type, ValueBytes};
+use tantivy_common::json_path_writer::{
+    JSON_END_OF_PATH, JSON_PATH_SEGMENT_SEP,
+};
 
 /// Tantivy's Occur
-#[pyclass(frozen, module = "tantivy.tantivy")]
-#[derive(Clone)]
+#[pyclass(frozen, eq, hash, module = "tantivy.tantivy")]
+#[derive(Clone, Deserialize, Hash, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
 pub enum Occur {
     Must,
     Should,
@@ -30,16 +43,115 @@ impl From<Occur> for tv::query::Occur {
     }
 }
 
+impl From<tv::query::Occur> for Occur {
+    fn from(occur: tv::query::Occur) -> Occur {
+        match occur {
+            tv::query::Occur::Must => Occur::Must,
+            tv::query::Occur::Should => Occur::Should,
+            tv::query::Occur::MustNot => Occur::MustNot,
+        }
+    }
+}
+
+/// Pairs the offsets of the terms of a phrase with their representations.
+fn offsets_with(
+    terms_with_offset: &[(usize, tv::schema::Term)],
+    terms: Vec<TermSpec>,
+) -> Vec<(usize, TermSpec)> {
+    terms_with_offset
+        .iter()
+        .map(|(offset, _)| *offset)
+        .zip(terms)
+        .collect()
+}
+
+/// Returns the name of the field of a term, followed by the path of the
+/// value for the terms of JSON fields, and its value.
+fn term_field_and_value(
+    schema: &tv::schema::Schema,
+    term: &Term,
+) -> Option<(String, Value)> {
+    let field_name = term
+        .get_full_path(schema)
+        .replace(char::from(JSON_PATH_SEGMENT_SEP), ".");
+    let mut value = term.value();
+    if term.typ() == Type::Json {
+        // The value follows the path, after its end marker.
+        let path_and_value = &term.serialized_value_bytes()[1..];
+        let end_of_path = path_and_value
+            .iter()
+            .position(|byte| *byte == JSON_END_OF_PATH)?;
+        value = ValueBytes::wrap(&path_and_value[end_of_path + 1..]);
+    }
+    let value = match value.typ() {
+        Type::Str => Value::Str(value.as_str()?.to_string()),
+        Type::U64 => Value::U64(value.as_u64()?),
+        Type::I64 => Value::I64(value.as_i64()?),
+        Type::F64 => Value::F64(value.as_f64()?),
+        Type::Bool => Value::Bool(value.as_bool()?),
+        Type::Date => Value::Date(value.as_date()?),
+        Type::Facet => Value::Facet(value.as_facet()?),
+        Type::Bytes => Value::Bytes(value.as_bytes()?.to_vec()),
+        Type::IpAddr => Value::IpAddr(value.as_ip_addr()?),
+        Type::Json => return None,
+    };
+    Some((field_name, value))
+}
+
+/// The boosts of a query and of its clauses, which tantivy does not expose.
+#[derive(Clone, Default)]
+pub(crate) struct QueryBoosts {
+    /// The boost of a boost query.
+    pub(crate) boost: Option<f32>,
+    /// The boosts of the clauses of a boolean query, which is empty when
+    /// they are unknown.
+    pub(crate) clauses: Vec<QueryBoosts>,
+}
+
+impl QueryBoosts {
+    fn of_spec(spec: &QuerySpec) -> QueryBoosts {
+        match spec {
+            QuerySpec::Boost { boost, .. } => QueryBoosts {
+                boost: Some(*boost),
+                clauses: Vec::new(),
+            },
+            QuerySpec::Boolean { subqueries, .. } => QueryBoosts {
+                boost: None,
+                clauses: subqueries
+                    .iter()
+                    .map(|(_, spec)| QueryBoosts::of_spec(spec))
+                    .collect(),
+            },
+            _ => QueryBoosts::default(),
+        }
+    }
+}
+
 /// Tantivy's Query
 #[pyclass(frozen, module = "tantivy.tantivy")]
 pub(crate) struct Query {
     pub(crate) inner: Box<dyn tv::query::Query>,
+    /// The serializable representation of the query, which is None for the
+    /// parsed queries and the queries on JSON fields.
+    pub(crate) spec: Option<QuerySpec>,
+    /// The schema the query was built for, which names the fields of its
+    /// terms. It is None when no constructor was given a schema.
+    pub(crate) schema: Option<tv::schema::Schema>,
+    /// A query matching the same documents without the boost and constant
+    /// score wrappers, which tantivy does not let look into. It is None
+    /// when the query has none of them.
+    pub(crate) matching: Option<Box<dyn tv::query::Query>>,
+    pub(crate) boosts: QueryBoosts,
 }
 
 impl Clone for Query {
     fn clone(&self) -> Self {
         Query {
             inner: self.inner.box_clone(),
+            spec: self.spec.clone(),
+            schema: self.schema.clone(),
+            matching: self.matching.as_ref().map(|query| query.box_clone()),
+            boosts: self.boosts.clone(),
         }
     }
 }
@@ -49,42 +161,81 @@ impl Query {
         &self.inner
     }
 
-    // Arguments mirror tantivy's MoreLikeThisQuery builder options one-to-one;
-    // a config struct here would only duplicate that builder.
-    #[allow(clippy::too_many_arguments)]
-    fn more_like_this_builder(
-        min_doc_frequency: Option<u64>,
-        max_doc_frequency: Option<u64>,
-        min_term_frequency: Option<usize>,
-        max_query_terms: Option<usize>,
-        min_word_length: Option<usize>,
-        max_word_length: Option<usize>,
-        boost_factor: Option<f32>,
-        stop_words: Vec<String>,
-    ) -> tv::query::MoreLikeThisQueryBuilder {
-        let mut builder = tv::query::MoreLikeThisQuery::builder();
-        if let Some(value) = min_doc_frequency {
-            builder = builder.with_min_doc_frequency(value);
-        }
-        if let Some(value) = max_doc_frequency {
-            builder = builder.with_max_doc_frequency(value);
-        }
-        if let Some(value) = min_term_frequency {
-            builder = builder.with_min_term_frequency(value);
-        }
-        if let Some(value) = max_query_terms {
-            builder = builder.with_max_query_terms(value);
-        }
-        if let Some(value) = min_word_length {
-            builder = builder.with_min_word_length(value);
-        }
-        if let Some(value) = max_word_length {
-            builder = builder.with_max_word_length(value);
+    fn new(
+        inner: impl tv::query::Query,
+        spec: Option<QuerySpec>,
+        schema: Option<&tv::schema::Schema>,
+    ) -> Query {
+        Query {
+            inner: Box::new(inner),
+            spec,
+            schema: schema.cloned(),
+            matching: None,
+            boosts: QueryBoosts::default(),
         }
-        if let Some(value) = boost_factor {
-            builder = builder.with_boost_factor(value);
+    }
+
+    fn with_matching(
+        mut self,
+        matching: Option<Box<dyn tv::query::Query>>,
+    ) -> Query {
+        self.matching = matching;
+        self
+    }
+
+    fn with_boosts(mut self, boosts: QueryBoosts) -> Query {
+        self.boosts = boosts;
+        self
+    }
+
+    /// Returns a query matching the same documents as this one, in which
+    /// tantivy lets look into every subquery.
+    pub(crate) fn matching_query(&self) -> &dyn tv::query::Query {
+        self.matching.as_deref().unwrap_or(self.inner.as_ref())
+    }
+
+    /// Returns a copy of the query matching the same documents, without
+    /// the boost and constant score wrappers.
+    fn unwrapped(&self) -> Query {
+        Query {
+            inner: self.matching_query().box_clone(),
+            spec: None,
+            schema: None,
+            matching: None,
+            boosts: QueryBoosts::default(),
         }
-        builder.with_stop_words(stop_words)
+    }
+
+    /// Returns the schema of the first query built for one.
+    fn schema_of<'a>(
+        queries: impl IntoIterator<Item = &'a Query>,
+    ) -> Option<&'a tv::schema::Schema> {
+        queries.into_iter().find_map(|query| query.schema.as_ref())
+    }
+
+    /// Returns the representation of a query whose terms can all be
+    /// represented.
+    fn spec_with_terms<'a>(
+        schema: &tv::schema::Schema,
+        terms: impl IntoIterator<Item = &'a tv::schema::Term>,
+        spec: impl FnOnce(Vec<TermSpec>) -> QuerySpec,
+    ) -> Option<QuerySpec> {
+        let terms = terms
+            .into_iter()
+            .map(|term| TermSpec::new(schema, term))
+            .collect::<PyResult<Vec<_>>>()
+            .ok()?;
+        Some(spec(terms))
+    }
+
+    /// Returns the representations of queries, if they all have one.
+    fn subquery_specs<'a>(
+        queries: impl IntoIterator<Item = &'a Query>,
+    ) -> Option<Vec<QuerySpec>> {
+        queries
+            .into_iter()
+            .map(|query| query.spec.clone())
+            .collect()
     }
 
     fn named_document_fields(
@@ -133,6 +284,24 @@ impl Query {
         if others.is_empty() {
             return self.clone();
         }
+        let matching = (self.matching.is_some()
+            || others.iter().any(|query| query.matching.is_some()))
+        .then(|| {
+            self.unwrapped()
+                .combine_with(
+                    others.iter().map(Query::unwrapped).collect(),
+                    self_occur,
+                    other_occur,
+                )
+                .inner
+        });
+        let other_specs = Query::subquery_specs(&others);
+        let other_boosts = others
+            .iter()
+            .map(|query| query.boosts.clone())
+            .collect::<Vec<_>>();
+        let schema =
+            Query::schema_of([self].into_iter().chain(&others)).cloned();
         let new_clauses =
             others.into_iter().map(|query| (other_occur, query.inner));
 
@@ -155,21 +324,69 @@ impl Query {
                     .map(|(occur, subquery)| (*occur, subquery.box_clone()))
                     .collect::<Vec<_>>();
                 subqueries.extend(new_clauses);
-                return Query {
-                    inner: Box::new(
-                        BooleanQuery::with_minimum_required_clauses(
-                            subqueries, minimum,
-                        ),
-                    ),
+                let mut clause_boosts = self.boosts.clauses.clone();
+                clause_boosts.resize(
+                    boolean_query.clauses().len(),
+                    QueryBoosts::default(),
+                );
+                clause_boosts.extend(other_boosts);
+                let spec = match (&self.spec, other_specs) {
+                    (
+                        Some(QuerySpec::Boolean { subqueries, .. }),
+                        Some(other_specs),
+                    ) => {
+                        let mut subqueries = subqueries.clone();
+                        subqueries.extend(
+                            other_specs
+                                .into_iter()
+                                .map(|spec| (other_occur.into(), spec)),
+                        );
+                        Some(QuerySpec::Boolean {
+                            subqueries,
+                            minimum_number_should_match: Some(minimum),
+                        })
+                    }
+                    _ => None,
                 };
+                return Query::new(
+                    BooleanQuery::with_minimum_required_clauses(
+                        subqueries, minimum,
+                    ),
+                    spec,
+                    schema.as_ref(),
+                )
+                .with_matching(matching)
+                .with_boosts(QueryBoosts {
+                    boost: None,
+                    clauses: clause_boosts,
+                });
             }
         }
 
+        let spec = self.spec.clone().zip(other_specs).map(
+            |(self_spec, other_specs)| {
+                let mut subqueries = vec![(self_occur.into(), self_spec)];
+                subqueries.extend(
+                    other_specs
+                        .into_iter()
+                        .map(|spec| (other_occur.into(), spec)),
+                );
+                QuerySpec::Boolean {
+                    subqueries,
+                    minimum_number_should_match: None,
+                }
+            },
+        );
         let mut subqueries = vec![(self_occur, self.inner.box_clone())];
         subqueries.extend(new_clauses);
-        Query {
-            inner: Box::new(BooleanQuery::new(subqueries)),
-        }
+        let mut clause_boosts = vec![self.boosts.clone()];
+        clause_boosts.extend(other_boosts);
+        Query::new(BooleanQuery::new(subqueries), spec, schema.as_ref())
+            .with_matching(matching)
+            .with_boosts(QueryBoosts {
+                boost: None,
+                clauses: clause_boosts,
+            })
     }
 }
 
@@ -179,6 +396,228 @@ impl Query {
         Ok(format!("Query({:?})", self.get()))
     }
 
+    /// The kind of the query, which is the `type` of its representation
+    /// returned by `Query.to_dict()`: 'term', 'boolean', 'range', etc.
+    #[getter]
+    fn kind(&self) -> &'static str {
+        use tv::query::*;
+        let query = self.inner.as_ref();
+        if query.is::<AllQuery>() {
+            "all"
+        } else if query.is::<EmptyQuery>() {
+            "empty"
+        } else if query.is::<TermQuery>() {
+            "term"
+        } else if query.is::<TermSetQuery>() {
+            "term_set"
+        } else if query.is::<ExistsQuery>() {
+            "exists"
+        } else if query.is::<FuzzyTermQuery>() {
+            "fuzzy_term"
+        } else if query.is::<PhraseQuery>() {
+            "phrase"
+        } else if query.is::<RegexPhraseQuery>() {
+            "regex_phrase"
+        } else if query.is::<PhrasePrefixQuery>() {
+            "phrase_prefix"
+        } else if query.is::<BooleanQuery>() {
+            "boolean"
+        } else if query.is::<DisjunctionMaxQuery>() {
+            "disjunction_max"
+        } else if query.is::<BoostQuery>() {
+            "boost"
+        } else if query.is::<ConstScoreQuery>() {
+            "const_score"
+        } else if query.is::<RegexQuery>() {
+            "regex"
+        } else if query.is::<RangeQuery>()
+            || query.is::<InvertedIndexRangeQuery>()
+        {
+            "range"
+        } else if query.is::<MoreLikeThisQuery>() {
+            "more_like_this"
+        } else {
+            "unknown"
+        }
+    }
+
+    /// The boost of a boost query, and 1.0 for the other queries. It is
+    /// None for the boost queries that tantivy's query parser built from
+    /// the boosts of the fields, which tantivy does not expose.
+    #[getter]
+    fn boost(&self) -> Option<f32> {
+        if !self.inner.is::<tv::query::BoostQuery>() {
+            return Some(1.0);
+        }
+        self.boosts.boost
+    }
+
+    /// Return the clauses of a boolean query, as a list of (Occur, Query)
+    /// pairs, and an empty list for the other queries.
+    fn clauses(&self) -> Vec<(Occur, Query)> {
+        let Some(query) = self.inner.downcast_ref::<tv::query::BooleanQuery>()
+        else {
+            return Vec::new();
+        };
+        let specs = match &self.spec {
+            Some(QuerySpec::Boolean { subqueries, .. }) => subqueries
+                .iter()
+                .map(|(_, spec)| Some(spec.clone()))
+                .collect(),
+            _ => vec![None; query.clauses().len()],
+        };
+        query
+            .clauses()
+            .iter()
+            .zip(specs)
+            .enumerate()
+            .map(|(i, ((occur, subquery), spec))| {
+                let subquery = Query {
+                    inner: subquery.box_clone(),
+                    spec,
+                    schema: self.schema.clone(),
+                    matching: None,
+                    boosts: self
+                        .boosts
+                        .clauses
+                        .get(i)
+                        .cloned()
+                        .unwrap_or_default(),
+                };
+                ((*occur).into(), subquery)
+            })
+            .collect()
+    }
+
+    /// Return the terms of the query, as a list of (field name, value)
+    /// pairs in the order of the query. The fields of JSON fields are
+    /// followed by the path of the value, e.g. 'attributes.color'.
+    ///
+    /// Only the terms tantivy reports for highlighting are returned, which
+    /// excludes the terms of fuzzy, regex and range queries.
+    ///
+    /// Args:
+    ///     searcher (Searcher, optional): If given, only the terms that
+    ///         occur in its documents are returned. Its schema names the
+    ///         fields of the queries not built with a schema.
+    #[pyo3(signature = (searcher = None))]
+    fn terms(
+        &self,
+        py: Python,
+        searcher: Option<&Searcher>,
+    ) -> PyResult<Vec<(String, Py<PyAny>)>> {
+        let mut terms: Vec<&Term> = Vec::new();
+        self.inner.query_terms(&mut |term, _| {
+            if !terms.contains(&term) {
+                terms.push(term);
+            }
+        });
+        if terms.is_empty() {
+            return Ok(Vec::new());
+        }
+        let schema = match (searcher, &self.schema) {
+            (Some(searcher), _) => searcher.inner.schema(),
+            (None, Some(schema)) => schema,
+            (None, None) => {
+                return Err(exceptions::PyValueError::new_err(
+                    "The query was not built with a schema, a searcher is \
+                     needed to name the fields of its terms.",
+                ))
+            }
+        };
+        let mut field_values = Vec::with_capacity(terms.len());
+        for term in terms {
+            if let Some(searcher) = searcher {
+                if searcher.inner.doc_freq(term).map_err(to_pyerr)? == 0 {
+                    continue;
+                }
+            }
+            let (field_name, value) = term_field_and_value(schema, term)
+                .ok_or_else(|| {
+                    exceptions::PyValueError::new_err(format!(
+                        "The term {term:?} cannot be converted."
+                    ))
+                })?;
+            field_values.push((field_name, value_to_py(py, &value)?));
+        }
+        Ok(field_values)
+    }
+
+    /// Return a representation of the query made of dicts, lists and
+    /// scalars, which can be converted to JSON and given to
+    /// `Query.from_dict()` to build the query again.
+    ///
+    /// Fields are referred to by their name, so the query can be built
+    /// again for any schema with fields of the same names and types.
+    ///
+    /// Raises a ValueError for the queries that cannot be represented: the
+    /// queries parsed from a query string, the queries on JSON fields, the
+    /// more like this queries of an indexed document, whose address is only
+    /// valid for its searcher, and the queries combining one of them.
+    fn to_dict(&self, py: Python) -> PyResult<Py<PyAny>> {
+        let spec = self.spec.as_ref().ok_or_else(|| {
+            exceptions::PyValueError::new_err(
+                "This query cannot be serialized: only the queries built with \
+                 the Query constructors, on fields other than JSON fields, \
+                 can be.",
+            )
+        })?;
+        Ok(pythonize::pythonize(py, spec).map_err(to_pyerr)?.unbind())
+    }
+
+    /// Build a query from the representation returned by `Query.to_dict()`.
+    ///
+    /// Args:
+    ///     schema (Schema): The schema to build the query for, which must
+    ///         have the fields the representation refers to.
+    ///     query_dict (dict): The representation of the query.
+    ///
+    /// Raises a ValueError if the representation is invalid, or refers to
+    /// a field missing from the schema or with values of another type.
+    #[staticmethod]
+    fn from_dict(
+        schema: &Schema,
+        query_dict: &Bound<PyAny>,
+    ) -> PyResult<Query> {
+        let spec: QuerySpec =
+            pythonize::depythonize(query_dict).map_err(to_pyerr)?;
+        let matching = spec
+            .without_wrappers()
+            .map(|spec| spec.build(&schema.inner))
+            .transpose()?;
+        let boosts = QueryBoosts::of_spec(&spec);
+        Ok(Query {
+            inner: spec.build(&schema.inner)?,
+            spec: Some(spec),
+            schema: Some(schema.inner.clone()),
+            matching,
+            boosts,
+        })
+    }
+
+    fn __reduce__<'a>(
+        slf: PyRef<'a, Self>,
+        py: Python<'a>,
+    ) -> PyResult<Bound<'a, PyTuple>> {
+        let query_dict = slf.to_dict(py)?.into_bound(py);
+        // The queries built without a schema do not refer to any field.
+        let schema = Schema {
+            inner: slf
+                .schema
+                .clone()
+                .unwrap_or_else(|| tv::schema::Schema::builder().build()),
+        };
+        let schema = schema.into_pyobject(py)?.into_any();
+        let from_dict = slf.into_pyobject(py)?.getattr("from_dict")?;
+        PyTuple::new(
+            py,
+            [
+                from_dict,
+                PyTuple::new(py, [schema, query_dict])?.into_any(),
+            ],
+        )
+    }
+
     /// Construct a Tantivy's TermQuery
     #[staticmethod]
     #[pyo3(signature = (schema, field_name, field_value, index_option = "position"))]
@@ -189,18 +628,15 @@ impl Query {
         index_option: &str,
     ) -> PyResult<Query> {
         let term = make_term(&schema.inner, field_name, field_value)?;
-        let index_option = match index_option {
-            "position" => tv::schema::IndexRecordOption::WithFreqsAndPositions,
-            "freq" => tv::schema::IndexRecordOption::WithFreqs,
-            "basic" => tv::schema::IndexRecordOption::Basic,
-            _ => return Err(exceptions::PyValueError::new_err(
-                "Invalid index option, valid choices are: 'basic', 'freq' and 'position'"
-            ))
-        };
+        let spec = TermSpec::new(&schema.inner, &term).ok().map(|term| {
+            QuerySpec::Term {
+                term,
+                index_option: index_option.to_string(),
+            }
+        });
+        let index_option = index_record_option(index_option)?;
         let inner = tv::query::TermQuery::new(term, index_option);
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, spec, Some(&schema.inner)))
     }
 
     /// Construct a Tantivy's TermSetQuery
@@ -217,28 +653,25 @@ impl Query {
                 make_term(&schema.inner, field_name, &field_value)
             })
             .collect::<Result<Vec<_>, _>>()?;
+        let spec = Query::spec_with_terms(&schema.inner, &terms, |terms| {
+            QuerySpec::TermSet { terms }
+        });
         let inner = tv::query::TermSetQuery::new(terms);
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, spec, Some(&schema.inner)))
     }
 
     /// Construct a Tantivy's AllQuery
     #[staticmethod]
     pub(crate) fn all_query() -> PyResult<Query> {
         let inner = tv::query::AllQuery {};
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, Some(QuerySpec::All), None))
     }
 
     /// Construct a Tantivy's EmptyQuery
     #[staticmethod]
     pub(crate) fn empty_query() -> PyResult<Query> {
         let inner = tv::query::EmptyQuery {};
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, Some(QuerySpec::Empty), None))
     }
 
     /// Construct a Tantivy's ExistsQuery
@@ -255,10 +688,12 @@ impl Query {
         fast_field_name: String,
         json_subpaths: bool,
     ) -> PyResult<Query> {
+        let spec = QuerySpec::Exists {
+            field: fast_field_name.clone(),
+            json_subpaths,
+        };
         let inner = tv::query::ExistsQuery::new(fast_field_name, json_subpaths);
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, Some(spec), None))
     }
 
     /// Construct a Tantivy's FuzzyTermQuery
@@ -282,6 +717,14 @@ impl Query {
         prefix: bool,
     ) -> PyResult<Query> {
         let term = make_term(&schema.inner, field_name, text)?;
+        let spec = TermSpec::new(&schema.inner, &term).ok().map(|term| {
+            QuerySpec::FuzzyTerm {
+                term,
+                distance,
+                transposition_cost_one,
+                prefix,
+            }
+        });
         let inner = if prefix {
             tv::query::FuzzyTermQuery::new_prefix(
                 term,
@@ -295,9 +738,7 @@ impl Query {
                 transposition_cost_one,
             )
         };
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, spec, Some(&schema.inner)))
     }
 
     /// Construct a Tantivy's PhraseQuery with custom offsets and slop
@@ -333,13 +774,19 @@ impl Query {
                 "words must not be empty.",
             ));
         }
+        let spec = Query::spec_with_terms(
+            &schema.inner,
+            terms_with_offset.iter().map(|(_, term)| term),
+            |terms| QuerySpec::Phrase {
+                terms: offsets_with(&terms_with_offset, terms),
+                slop,
+            },
+        );
         let inner = tv::query::PhraseQuery::new_with_offset_and_slop(
             terms_with_offset,
             slop,
         );
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, spec, Some(&schema.inner)))
     }
 
     /// Construct a Tantivy's PhraseQuery with custom offsets and slop
@@ -379,14 +826,17 @@ impl Query {
                 "words must not be empty.",
             ));
         }
+        let spec = QuerySpec::RegexPhrase {
+            field: field_name.to_string(),
+            words: terms_with_offset.clone(),
+            slop,
+        };
         let inner = tv::query::RegexPhraseQuery::new_with_offset_and_slop(
             field,
             terms_with_offset,
             slop,
         );
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, Some(spec), Some(&schema.inner)))
     }
 
     /// Construct a Tantivy's PhrasePrefixQuery with custom offsets and slop
@@ -420,11 +870,16 @@ impl Query {
                 "words must not be empty.",
             ));
         }
+        let spec = Query::spec_with_terms(
+            &schema.inner,
+            terms_with_offset.iter().map(|(_, term)| term),
+            |terms| QuerySpec::PhrasePrefix {
+                terms: offsets_with(&terms_with_offset, terms),
+            },
+        );
         let inner =
             tv::query::PhrasePrefixQuery::new_with_offset(terms_with_offset);
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, spec, Some(&schema.inner)))
     }
 
     /// Construct a Tantivy's BooleanQuery
@@ -434,21 +889,54 @@ impl Query {
         subqueries: Vec<(Occur, Query)>,
         minimum_number_should_match: Option<usize>,
     ) -> PyResult<Query> {
+        let spec = subqueries
+            .iter()
+            .map(|(occur, query)| Some((occur.clone(), query.spec.clone()?)))
+            .collect::<Option<Vec<_>>>()
+            .map(|subqueries| QuerySpec::Boolean {
+                subqueries,
+                minimum_number_should_match,
+            });
+        let schema =
+            Query::schema_of(subqueries.iter().map(|(_, query)| query))
+                .cloned();
+        let build = |subqueries| match minimum_number_should_match {
+            None => tv::query::BooleanQuery::from(subqueries),
+            Some(n) => tv::query::BooleanQuery::with_minimum_required_clauses(
+                subqueries, n,
+            ),
+        };
+        let matching = subqueries
+            .iter()
+            .any(|(_, query)| query.matching.is_some())
+            .then(|| {
+                let subqueries = subqueries
+                    .iter()
+                    .map(|(occur, query)| {
+                        (
+                            occur.clone().into(),
+                            query.matching_query().box_clone(),
+                        )
+                    })
+                    .collect();
+                Box::new(build(subqueries)) as Box<dyn tv::query::Query>
+            });
+        let boosts = QueryBoosts {
+            boost: None,
+            clauses: subqueries
+                .iter()
+                .map(|(_, query)| query.boosts.clone())
+                .collect(),
+        };
         let dyn_subqueries = subqueries
             .into_iter()
             .map(|(occur, query)| (occur.into(), query.inner.box_clone()))
             .collect::<Vec<_>>();
 
-        let inner = match minimum_number_should_match {
-            None => tv::query::BooleanQuery::from(dyn_subqueries),
-            Some(n) => tv::query::BooleanQuery::with_minimum_required_clauses(
-                dyn_subqueries,
-                n,
-            ),
-        };
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        let inner = build(dyn_subqueries);
+        Ok(Query::new(inner, spec, schema.as_ref())
+            .with_matching(matching)
+            .with_boosts(boosts))
     }
 
     /// Convenience method to combine queries with AND (MUST) logic.
@@ -511,28 +999,60 @@ impl Query {
             .map(|query| query.inner.box_clone())
             .collect();
 
-        let dismax_query = if let Some(tie_breaker) = tie_breaker {
-            tv::query::DisjunctionMaxQuery::with_tie_breaker(
-                inner_queries,
-                tie_breaker.extract::<f32>()?,
-            )
-        } else {
-            tv::query::DisjunctionMaxQuery::new(inner_queries)
+        let tie_breaker = tie_breaker
+            .map(|tie_breaker| tie_breaker.extract::<f32>())
+            .transpose()?;
+        let spec = Query::subquery_specs(&subqueries).map(|subqueries| {
+            QuerySpec::DisjunctionMax {
+                subqueries,
+                tie_breaker,
+            }
+        });
+
+        let build = |queries| {
+            if let Some(tie_breaker) = tie_breaker {
+                tv::query::DisjunctionMaxQuery::with_tie_breaker(
+                    queries,
+                    tie_breaker,
+                )
+            } else {
+                tv::query::DisjunctionMaxQuery::new(queries)
+            }
         };
+        let matching = subqueries
+            .iter()
+            .any(|query| query.matching.is_some())
+            .then(|| {
+                let queries = subqueries
+                    .iter()
+                    .map(|query| query.matching_query().box_clone())
+                    .collect();
+                Box::new(build(queries)) as Box<dyn tv::query::Query>
+            });
+        let dismax_query = build(inner_queries);
 
-        Ok(Query {
-            inner: Box::new(dismax_query),
-        })
+        Ok(
+            Query::new(dismax_query, spec, Query::schema_of(&subqueries))
+                .with_matching(matching),
+        )
     }
 
     /// Construct a Tantivy's BoostQuery
     #[staticmethod]
     #[pyo3(signature = (query, boost))]
     pub(crate) fn boost_query(query: Query, boost: f32) -> PyResult<Query> {
+        let matching = query.matching_query().box_clone();
+        let spec = query.spec.map(|query| QuerySpec::Boost {
+            query: Box::new(query),
+            boost,
+        });
         let inner = tv::query::BoostQuery::new(query.inner, boost);
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, spec, query.schema.as_ref())
+            .with_matching(Some(matching))
+            .with_boosts(QueryBoosts {
+                boost: Some(boost),
+                clauses: Vec::new(),
+            }))
     }
 
     /// Construct a Tantivy's RegexQuery
@@ -547,10 +1067,12 @@ impl Query {
 
         let inner_result =
             tv::query::RegexQuery::from_pattern(regex_pattern, field);
+        let spec = QuerySpec::Regex {
+            field: field_name.to_string(),
+            pattern: regex_pattern.to_string(),
+        };
         match inner_result {
-            Ok(inner) => Ok(Query {
-                inner: Box::new(inner),
-            }),
+            Ok(inner) => Ok(Query::new(inner, Some(spec), Some(&schema.inner))),
             Err(e) => Err(to_pyerr(e)),
         }
     }
@@ -569,7 +1091,7 @@ impl Query {
         boost_factor: Option<f32>,
         stop_words: Vec<String>,
     ) -> PyResult<Query> {
-        let builder = Query::more_like_this_builder(
+        let options = MoreLikeThisOptions {
             min_doc_frequency,
             max_doc_frequency,
             min_term_frequency,
@@ -578,12 +1100,14 @@ impl Query {
             max_word_length,
             boost_factor,
             stop_words,
-        );
+        };
 
-        let inner = builder.with_document(tv::DocAddress::from(doc_address));
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        let inner = options
+            .builder()
+            .with_document(tv::DocAddress::from(doc_address));
+        // The address of the document is only valid for its searcher, so
+        // the query cannot be serialized.
+        Ok(Query::new(inner, None, None))
     }
 
     /// Construct a Tantivy's MoreLikeThisQuery from caller-provided field values.
@@ -606,7 +1130,7 @@ impl Query {
         // Python binding must resolve caller-provided field values against the
         // target schema before constructing the query object.
         let doc_fields = Query::named_document_fields(schema, document_fields)?;
-        let builder = Query::more_like_this_builder(
+        let options = MoreLikeThisOptions {
             min_doc_frequency,
             max_doc_frequency,
             min_term_frequency,
@@ -615,12 +1139,23 @@ impl Query {
             max_word_length,
             boost_factor,
             stop_words,
-        );
+        };
 
-        let inner = builder.with_document_fields(doc_fields);
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        let document_fields = doc_fields
+            .iter()
+            .map(|(field, values)| {
+                (
+                    schema.inner.get_field_name(*field).to_string(),
+                    values.iter().cloned().map(Into::into).collect(),
+                )
+            })
+            .collect();
+        let inner = options.builder().with_document_fields(doc_fields);
+        let spec = QuerySpec::MoreLikeThis {
+            document_fields,
+            options,
+        };
+        Ok(Query::new(inner, Some(spec), Some(&schema.inner)))
     }
 
     /// Construct a Tantivy's ConstScoreQuery
@@ -630,10 +1165,14 @@ impl Query {
         query: Query,
         score: f32,
     ) -> PyResult<Query> {
+        let matching = query.matching_query().box_clone();
+        let spec = query.spec.map(|query| QuerySpec::ConstScore {
+            query: Box::new(query),
+            score,
+        });
         let inner = tv::query::ConstScoreQuery::new(query.inner, score);
-        Ok(Query {
-            inner: Box::new(inner),
-        })
+        Ok(Query::new(inner, spec, query.schema.as_ref())
+            .with_matching(Some(matching)))
     }
 
     /// Construct a range query over a numeric, date, or IP address field.
@@ -767,6 +1306,16 @@ impl Query {
             }
         };
 
+        let spec = RangeBound::new(&schema.inner, &lower_bound)
+            .and_then(|lower| {
+                Ok((lower, RangeBound::new(&schema.inner, &upper_bound)?))
+            })
+            .ok()
+            .map(|(lower_bound, upper_bound)| QuerySpec::Range {
+                lower_bound,
+                upper_bound,
+                use_inverted_index,
+            });
         let inner: Box<dyn tv::query::Query> = if use_inverted_index {
             Box::new(tv::query::InvertedIndexRangeQuery::new(
                 lower_bound,
@@ -775,7 +1324,13 @@ impl Query {
         } else {
             Box::new(tv::query::RangeQuery::new(lower_bound, upper_bound))
         };
-        Ok(Query { inner })
+        Ok(Query {
+            inner,
+            spec,
+            schema: Some(schema.inner.clone()),
+            matching: None,
+            boosts: QueryBoosts::default(),
+        })
     }
 
     /// Explain how this query matches a given document.
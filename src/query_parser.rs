@@ -0,0 +1,174 @@
+use pyo3::prelude::*;
+use tantivy::{
+    self as tv,
+    query::{BooleanQuery, BoostQuery, Occur, QueryParserError},
+    query_grammar::UserInputAst,
+    schema::Field,
+    Score,
+};
+
+use crate::{
+    query::{Query, QueryBoosts},
+    to_pyerr,
+};
+
+/// Tantivy's query parser, along with the field boosts it was given. The
+/// queries it builds record the boosts of the parsed query, and a query
+/// matching the same documents without any boost, which tantivy does not
+/// let look into.
+pub(crate) struct QueryParser {
+    inner: tv::query::QueryParser,
+    schema: tv::schema::Schema,
+    field_boosts: Vec<(Field, Score)>,
+    default_occur: Occur,
+}
+
+impl QueryParser {
+    pub(crate) fn new(
+        inner: tv::query::QueryParser,
+        schema: tv::schema::Schema,
+        field_boosts: Vec<(Field, Score)>,
+        conjunction_by_default: bool,
+    ) -> Self {
+        QueryParser {
+            inner,
+            schema,
+            field_boosts,
+            default_occur: if conjunction_by_default {
+                Occur::Must
+            } else {
+                Occur::Should
+            },
+        }
+    }
+
+    pub(crate) fn parse_query(&self, query: &str) -> PyResult<Query> {
+        let inner = self.inner.parse_query(query).map_err(to_pyerr)?;
+        let ast = tv::query_grammar::parse_query(query).ok();
+        Ok(self.query(inner, ast))
+    }
+
+    pub(crate) fn parse_query_lenient(
+        &self,
+        query: &str,
+    ) -> (Query, Vec<QueryParserError>) {
+        let (inner, errors) = self.inner.parse_query_lenient(query);
+        let (ast, _) = tv::query_grammar::parse_query_lenient(query);
+        (self.query(inner, Some(ast)), errors)
+    }
+
+    /// Returns `inner`, built by tantivy from the parsed query `ast`.
+    fn query(
+        &self,
+        inner: Box<dyn tv::query::Query>,
+        ast: Option<UserInputAst>,
+    ) -> Query {
+        let mut query = Query {
+            inner,
+            spec: None,
+            schema: Some(self.schema.clone()),
+            matching: None,
+            boosts: QueryBoosts::default(),
+        };
+        let Some(ast) = ast else {
+            return query;
+        };
+        let unboosted = without_boosts(&ast);
+        if unboosted.is_none() && self.field_boosts.is_empty() {
+            return query;
+        }
+        query.boosts = self.boosts(&ast, query.inner.as_ref());
+        let mut parser = self.inner.clone();
+        for (field, _) in &self.field_boosts {
+            parser.set_field_boost(*field, 1.0);
+        }
+        query.matching = Some(
+            parser
+                .build_query_from_user_input_ast_lenient(
+                    unboosted.unwrap_or(ast),
+                )
+                .0,
+        );
+        query
+    }
+
+    /// Returns the boosts of `query` given by the boosts of the parsed query
+    /// `ast` it was built from. A boost is only known where the query has
+    /// the structure of `ast`, and the boosts of the fields are not.
+    fn boosts(
+        &self,
+        ast: &UserInputAst,
+        query: &dyn tv::query::Query,
+    ) -> QueryBoosts {
+        match ast {
+            // tantivy does not wrap the queries boosted by 1.
+            UserInputAst::Boost(ast, boost)
+                if (boost.into_inner() as Score - 1.0).abs()
+                    < Score::EPSILON =>
+            {
+                self.boosts(ast, query)
+            }
+            UserInputAst::Boost(_, boost) if query.is::<BoostQuery>() => {
+                QueryBoosts {
+                    boost: Some(boost.into_inner() as Score),
+                    clauses: Vec::new(),
+                }
+            }
+            UserInputAst::Clause(asts) => {
+                let Some(query) = query.downcast_ref::<BooleanQuery>() else {
+                    return QueryBoosts::default();
+                };
+                let clauses = query.clauses();
+                if clauses.len() != asts.len()
+                    || clauses.iter().zip(asts).any(
+                        |((occur, _), (ast_occur, _))| {
+                            *occur != ast_occur.unwrap_or(self.default_occur)
+                        },
+                    )
+                {
+                    return QueryBoosts::default();
+                }
+                QueryBoosts {
+                    boost: None,
+                    clauses: clauses
+                        .iter()
+                        .zip(asts)
+                        .map(|((_, clause), (_, ast))| {
+                            self.boosts(ast, clause.as_ref())
+                        })
+                        .collect(),
+                }
+            }
+            _ => QueryBoosts::default(),
+        }
+    }
+}
+
+/// Returns the parsed query `ast` without its boosts, or None if it has
+/// none of them.
+fn without_boosts(ast: &UserInputAst) -> Option<UserInputAst> {
+    match ast {
+        UserInputAst::Boost(ast, _) => {
+            Some(without_boosts(ast).unwrap_or_else(|| (**ast).clone()))
+        }
+        UserInputAst::Clause(clauses) => {
+            let unboosted = clauses
+                .iter()
+                .map(|(_, ast)| without_boosts(ast))
+                .collect::<Vec<_>>();
+            if unboosted.iter().all(Option::is_none) {
+                return None;
+            }
+            Some(UserInputAst::Clause(
+                clauses
+                    .iter()
+                    .zip(unboosted)
+                    .map(|((occur, ast), unboosted)| {
+                        (*occur, unboosted.unwrap_or_else(|| ast.clone()))
+                    })
+                    .collect(),
+            ))
+        }
+        UserInputAst::Leaf(_) => None,
+    }
+}
@@ -0,0 +1,589 @@
+use std::{net::Ipv6Addr, ops::Bound as OpsBound, str::FromStr};
+
+use pyo3::{exceptions, prelude::*};
+use serde::{Deserialize, Serialize};
+use tantivy::{
+    self as tv,
+    schema::{Facet, Field, IndexRecordOption, Term, Type},
+};
+
+use crate::{document::SerdeValue, query::Occur, to_pyerr};
+
+/// Parses the `index_option` argument of `Query.term_query()`.
+pub(crate) fn index_record_option(
+    index_option: &str,
+) -> PyResult<IndexRecordOption> {
+    match index_option {
+        "position" => Ok(IndexRecordOption::WithFreqsAndPositions),
+        "freq" => Ok(IndexRecordOption::WithFreqs),
+        "basic" => Ok(IndexRecordOption::Basic),
+        _ => Err(exceptions::PyValueError::new_err(
+            "Invalid index option, valid choices are: 'basic', 'freq' and 'position'"
+        )),
+    }
+}
+
+fn invalid_spec(message: &str) -> PyErr {
+    exceptions::PyValueError::new_err(format!("Invalid query dict: {message}"))
+}
+
+/// Returns the field of `schema` named `field_name`, checking that its
+/// values have the type `typ` if given, as tantivy panics on unknown fields
+/// and may on terms of another type.
+fn schema_field(
+    schema: &tv::schema::Schema,
+    field_name: &str,
+    typ: Option<Type>,
+) -> PyResult<Field> {
+    let field = schema.get_field(field_name).map_err(|_| {
+        invalid_spec(&format!("the schema has no field `{field_name}`."))
+    })?;
+    let field_entry = schema.get_field_entry(field);
+    let field_type = field_entry.field_type().value_type();
+    match typ {
+        Some(typ) if typ != field_type => Err(invalid_spec(&format!(
+            "the field `{}` holds {field_type:?} values, not {typ:?}.",
+            field_entry.name()
+        ))),
+        _ => Ok(field),
+    }
+}
+
+/// The value of a term. Dates are timestamps in nanoseconds, and bytes are
+/// hex encoded so that the representation can be converted to JSON.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+enum TermValue {
+    Str(String),
+    U64(u64),
+    I64(i64),
+    F64(f64),
+    Bool(bool),
+    Date(i64),
+    Facet(String),
+    Bytes(String),
+    IpAddr(String),
+}
+
+/// A term, with the name of its field.
+#[derive(Clone, Deserialize, Serialize)]
+pub(crate) struct TermSpec {
+    field: String,
+    #[serde(flatten)]
+    value: TermValue,
+}
+
+impl TermSpec {
+    pub(crate) fn new(
+        schema: &tv::schema::Schema,
+        term: &Term,
+    ) -> PyResult<Self> {
+        let value = term.value();
+        let value = match term.typ() {
+            Type::Str => value.as_str().map(|v| TermValue::Str(v.to_string())),
+            Type::U64 => value.as_u64().map(TermValue::U64),
+            Type::I64 => value.as_i64().map(TermValue::I64),
+            Type::F64 => value.as_f64().map(TermValue::F64),
+            Type::Bool => value.as_bool().map(TermValue::Bool),
+            Type::Date => value
+                .as_date()
+                .map(|v| TermValue::Date(v.into_timestamp_nanos())),
+            Type::Facet => value
+                .as_facet()
+                .map(|v| TermValue::Facet(v.to_path_string())),
+            Type::Bytes => value.as_bytes().map(|v| {
+                TermValue::Bytes(v.iter().map(|b| format!("{b:02x}")).collect())
+            }),
+            Type::IpAddr => {
+                value.as_ip_addr().map(|v| TermValue::IpAddr(v.to_string()))
+            }
+            Type::Json => None,
+        };
+        let value = value.ok_or_else(|| {
+            exceptions::PyValueError::new_err(format!(
+                "Terms of type {:?} cannot be serialized.",
+                term.typ()
+            ))
+        })?;
+        Ok(TermSpec {
+            field: schema.get_field_name(term.field()).to_string(),
+            value,
+        })
+    }
+
+    fn build(&self, schema: &tv::schema::Schema) -> PyResult<Term> {
+        let typ = match &self.value {
+            TermValue::Str(_) => Type::Str,
+            TermValue::U64(_) => Type::U64,
+            TermValue::I64(_) => Type::I64,
+            TermValue::F64(_) => Type::F64,
+            TermValue::Bool(_) => Type::Bool,
+            TermValue::Date(_) => Type::Date,
+            TermValue::Facet(_) => Type::Facet,
+            TermValue::Bytes(_) => Type::Bytes,
+            TermValue::IpAddr(_) => Type::IpAddr,
+        };
+        let field = schema_field(schema, &self.field, Some(typ))?;
+        Ok(match &self.value {
+            TermValue::Str(v) => Term::from_field_text(field, v),
+            TermValue::U64(v) => Term::from_field_u64(field, *v),
+            TermValue::I64(v) => Term::from_field_i64(field, *v),
+            TermValue::F64(v) => Term::from_field_f64(field, *v),
+            TermValue::Bool(v) => Term::from_field_bool(field, *v),
+            TermValue::Date(v) => Term::from_field_date(
+                field,
+                tv::DateTime::from_timestamp_nanos(*v),
+            ),
+            TermValue::Facet(v) => Term::from_facet(
+                field,
+                &Facet::from_text(v)
+                    .map_err(|err| invalid_spec(&err.to_string()))?,
+            ),
+            TermValue::Bytes(v) => {
+                let bytes = (0..v.len())
+                    .step_by(2)
+                    .map(|i| {
+                        v.get(i..i + 2)
+                            .and_then(|b| u8::from_str_radix(b, 16).ok())
+                    })
+                    .collect::<Option<Vec<u8>>>()
+                    .ok_or_else(|| {
+                        invalid_spec("bytes must be hex encoded.")
+                    })?;
+                Term::from_field_bytes(field, &bytes)
+            }
+            TermValue::IpAddr(v) => Term::from_field_ip_addr(
+                field,
+                Ipv6Addr::from_str(v)
+                    .map_err(|err| invalid_spec(&err.to_string()))?,
+            ),
+        })
+    }
+}
+
+fn build_terms(
+    terms: &[(usize, TermSpec)],
+    schema: &tv::schema::Schema,
+) -> PyResult<Vec<(usize, Term)>> {
+    let terms = terms
+        .iter()
+        .map(|(offset, term)| Ok((*offset, term.build(schema)?)))
+        .collect::<PyResult<Vec<_>>>()?;
+    if terms
+        .iter()
+        .any(|(_, term)| term.field() != terms[0].1.field())
+    {
+        return Err(invalid_spec(
+            "the terms of a phrase must belong to the same field.",
+        ));
+    }
+    Ok(terms)
+}
+
+/// A bound of a range query.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub(crate) enum RangeBound {
+    Included(TermSpec),
+    Excluded(TermSpec),
+    Unbounded,
+}
+
+impl RangeBound {
+    pub(crate) fn new(
+        schema: &tv::schema::Schema,
+        bound: &OpsBound<Term>,
+    ) -> PyResult<Self> {
+        Ok(match bound {
+            OpsBound::Included(term) => {
+                RangeBound::Included(TermSpec::new(schema, term)?)
+            }
+            OpsBound::Excluded(term) => {
+                RangeBound::Excluded(TermSpec::new(schema, term)?)
+            }
+            OpsBound::Unbounded => RangeBound::Unbounded,
+        })
+    }
+
+    fn build(&self, schema: &tv::schema::Schema) -> PyResult<OpsBound<Term>> {
+        Ok(match self {
+            RangeBound::Included(term) => {
+                OpsBound::Included(term.build(schema)?)
+            }
+            RangeBound::Excluded(term) => {
+                OpsBound::Excluded(term.build(schema)?)
+            }
+            RangeBound::Unbounded => OpsBound::Unbounded,
+        })
+    }
+}
+
+/// The options of a more like this query.
+#[derive(Clone, Deserialize, Serialize)]
+pub(crate) struct MoreLikeThisOptions {
+    pub(crate) min_doc_frequency: Option<u64>,
+    pub(crate) max_doc_frequency: Option<u64>,
+    pub(crate) min_term_frequency: Option<usize>,
+    pub(crate) max_query_terms: Option<usize>,
+    pub(crate) min_word_length: Option<usize>,
+    pub(crate) max_word_length: Option<usize>,
+    pub(crate) boost_factor: Option<f32>,
+    pub(crate) stop_words: Vec<String>,
+}
+
+impl MoreLikeThisOptions {
+    pub(crate) fn builder(&self) -> tv::query::MoreLikeThisQueryBuilder {
+        let mut builder = tv::query::MoreLikeThisQuery::builder();
+        if let Some(value) = self.min_doc_frequency {
+            builder = builder.with_min_doc_frequency(value);
+        }
+        if let Some(value) = self.max_doc_frequency {
+            builder = builder.with_max_doc_frequency(value);
+        }
+        if let Some(value) = self.min_term_frequency {
+            builder = builder.with_min_term_frequency(value);
+        }
+        if let Some(value) = self.max_query_terms {
+            builder = builder.with_max_query_terms(value);
+        }
+        if let Some(value) = self.min_word_length {
+            builder = builder.with_min_word_length(value);
+        }
+        if let Some(value) = self.max_word_length {
+            builder = builder.with_max_word_length(value);
+        }
+        if let Some(value) = self.boost_factor {
+            builder = builder.with_boost_factor(value);
+        }
+        builder.with_stop_words(self.stop_words.clone())
+    }
+}
+
+/// Serializable representation of the queries built with the constructors
+/// of `Query`, from which the tantivy query can be built again.
+///
+/// Fields are referred to by their name. The more like this queries of an
+/// indexed document have no representation, since its address is only
+/// valid for the searcher it comes from.
+#[derive(Clone, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub(crate) enum QuerySpec {
+    All,
+    Empty,
+    Term {
+        term: TermSpec,
+        index_option: String,
+    },
+    TermSet {
+        terms: Vec<TermSpec>,
+    },
+    Exists {
+        field: String,
+        json_subpaths: bool,
+    },
+    FuzzyTerm {
+        term: TermSpec,
+        distance: u8,
+        transposition_cost_one: bool,
+        prefix: bool,
+    },
+    Phrase {
+        terms: Vec<(usize, TermSpec)>,
+        slop: u32,
+    },
+    RegexPhrase {
+        field: String,
+        words: Vec<(usize, String)>,
+        slop: u32,
+    },
+    PhrasePrefix {
+        terms: Vec<(usize, TermSpec)>,
+    },
+    Boolean {
+        subqueries: Vec<(Occur, QuerySpec)>,
+        minimum_number_should_match: Option<usize>,
+    },
+    DisjunctionMax {
+        subqueries: Vec<QuerySpec>,
+        tie_breaker: Option<f32>,
+    },
+    Boost {
+        query: Box<QuerySpec>,
+        boost: f32,
+    },
+    ConstScore {
+        query: Box<QuerySpec>,
+        score: f32,
+    },
+    Regex {
+        field: String,
+        pattern: String,
+    },
+    Range {
+        lower_bound: RangeBound,
+        upper_bound: RangeBound,
+        use_inverted_index: bool,
+    },
+    MoreLikeThis {
+        /// The values of the fields of the document, by field name.
+        document_fields: Vec<(String, Vec<SerdeValue>)>,
+        options: MoreLikeThisOptions,
+    },
+}
+
+impl QuerySpec {
+    /// Returns the representation of a query matching the same documents
+    /// without the boost and constant score wrappers, or None if it has
+    /// none of them.
+    pub(crate) fn without_wrappers(&self) -> Option<QuerySpec> {
+        match self {
+            QuerySpec::Boost { query, .. }
+            | QuerySpec::ConstScore { query, .. } => Some(
+                query
+                    .without_wrappers()
+                    .unwrap_or_else(|| (**query).clone()),
+            ),
+            QuerySpec::Boolean {
+                subqueries,
+                minimum_number_should_match,
+            } => {
+                let unwrapped = subqueries
+                    .iter()
+                    .map(|(_, query)| query.without_wrappers())
+                    .collect::<Vec<_>>();
+                if unwrapped.iter().all(Option::is_none) {
+                    return None;
+                }
+                Some(QuerySpec::Boolean {
+                    subqueries: subqueries
+                        .iter()
+                        .zip(unwrapped)
+                        .map(|((occur, query), unwrapped)| {
+                            (
+                                occur.clone(),
+                                unwrapped.unwrap_or_else(|| query.clone()),
+                            )
+                        })
+                        .collect(),
+                    minimum_number_should_match: *minimum_number_should_match,
+                })
+            }
+            QuerySpec::DisjunctionMax {
+                subqueries,
+                tie_breaker,
+            } => {
+                let unwrapped = subqueries
+                    .iter()
+                    .map(QuerySpec::without_wrappers)
+                    .collect::<Vec<_>>();
+                if unwrapped.iter().all(Option::is_none) {
+                    return None;
+                }
+                Some(QuerySpec::DisjunctionMax {
+                    subqueries: subqueries
+                        .iter()
+                        .zip(unwrapped)
+                        .map(|(query, unwrapped)| {
+                            unwrapped.unwrap_or_else(|| query.clone())
+                        })
+                        .collect(),
+                    tie_breaker: *tie_breaker,
+                })
+            }
+            _ => None,
+        }
+    }
+
+    /// Builds the tantivy query for `schema`, checking the arguments that
+    /// tantivy asserts.
+    pub(crate) fn build(
+        &self,
+        schema: &tv::schema::Schema,
+    ) -> PyResult<Box<dyn tv::query::Query>> {
+        Ok(match self {
+            QuerySpec::All => Box::new(tv::query::AllQuery),
+            QuerySpec::Empty => Box::new(tv::query::EmptyQuery),
+            QuerySpec::Term { term, index_option } => {
+                Box::new(tv::query::TermQuery::new(
+                    term.build(schema)?,
+                    index_record_option(index_option)?,
+                ))
+            }
+            QuerySpec::TermSet { terms } => {
+                Box::new(tv::query::TermSetQuery::new(
+                    terms
+                        .iter()
+                        .map(|term| term.build(schema))
+                        .collect::<PyResult<Vec<_>>>()?,
+                ))
+            }
+            QuerySpec::Exists {
+                field,
+                json_subpaths,
+            } => {
+                // The field may be a path in a JSON field.
+                if schema.find_field(field).is_none() {
+                    return Err(invalid_spec(&format!(
+                        "the schema has no field `{field}`."
+                    )));
+                }
+                Box::new(tv::query::ExistsQuery::new(
+                    field.clone(),
+                    *json_subpaths,
+                ))
+            }
+            QuerySpec::FuzzyTerm {
+                term,
+                distance,
+                transposition_cost_one,
+                prefix,
+            } => {
+                let term = term.build(schema)?;
+                Box::new(if *prefix {
+                    tv::query::FuzzyTermQuery::new_prefix(
+                        term,
+                        *distance,
+                        *transposition_cost_one,
+                    )
+                } else {
+                    tv::query::FuzzyTermQuery::new(
+                        term,
+                        *distance,
+                        *transposition_cost_one,
+                    )
+                })
+            }
+            QuerySpec::Phrase { terms, slop } => {
+                if terms.len() < 2 {
+                    return Err(invalid_spec(
+                        "a phrase query needs at least two terms.",
+                    ));
+                }
+                Box::new(tv::query::PhraseQuery::new_with_offset_and_slop(
+                    build_terms(terms, schema)?,
+                    *slop,
+                ))
+            }
+            QuerySpec::RegexPhrase { field, words, slop } => {
+                if words.len() < 2 {
+                    return Err(invalid_spec(
+                        "a phrase query needs at least two terms.",
+                    ));
+                }
+                Box::new(tv::query::RegexPhraseQuery::new_with_offset_and_slop(
+                    schema_field(schema, field, None)?,
+                    words.clone(),
+                    *slop,
+                ))
+            }
+            QuerySpec::PhrasePrefix { terms } => {
+                if terms.is_empty() {
+                    return Err(invalid_spec(
+                        "a phrase prefix query needs at least one term.",
+                    ));
+                }
+                Box::new(tv::query::PhrasePrefixQuery::new_with_offset(
+                    build_terms(terms, schema)?,
+                ))
+            }
+            QuerySpec::Boolean {
+                subqueries,
+                minimum_number_should_match,
+            } => {
+                let subqueries = subqueries
+                    .iter()
+                    .map(|(occur, query)| {
+                        Ok((occur.clone().into(), query.build(schema)?))
+                    })
+                    .collect::<PyResult<Vec<_>>>()?;
+                Box::new(match minimum_number_should_match {
+                    None => tv::query::BooleanQuery::from(subqueries),
+                    Some(n) => {
+                        tv::query::BooleanQuery::with_minimum_required_clauses(
+                            subqueries, *n,
+                        )
+                    }
+                })
+            }
+            QuerySpec::DisjunctionMax {
+                subqueries,
+                tie_breaker,
+            } => {
+                let subqueries = subqueries
+                    .iter()
+                    .map(|query| query.build(schema))
+                    .collect::<PyResult<Vec<_>>>()?;
+                Box::new(match tie_breaker {
+                    None => tv::query::DisjunctionMaxQuery::new(subqueries),
+                    Some(tie_breaker) => {
+                        tv::query::DisjunctionMaxQuery::with_tie_breaker(
+                            subqueries,
+                            *tie_breaker,
+                        )
+                    }
+                })
+            }
+            QuerySpec::Boost { query, boost } => Box::new(
+                tv::query::BoostQuery::new(query.build(schema)?, *boost),
+            ),
+            QuerySpec::ConstScore { query, score } => Box::new(
+                tv::query::ConstScoreQuery::new(query.build(schema)?, *score),
+            ),
+            QuerySpec::Regex { field, pattern } => Box::new(
+                tv::query::RegexQuery::from_pattern(
+                    pattern,
+                    schema_field(schema, field, None)?,
+                )
+                .map_err(to_pyerr)?,
+            ),
+            QuerySpec::Range {
+                lower_bound,
+                upper_bound,
+                use_inverted_index,
+            } => {
+                let lower_bound = lower_bound.build(schema)?;
+                let upper_bound = upper_bound.build(schema)?;
+                // tantivy panics when both bounds are unbounded.
+                if matches!(
+                    (&lower_bound, &upper_bound),
+                    (OpsBound::Unbounded, OpsBound::Unbounded)
+                ) {
+                    return Err(invalid_spec(
+                        "a range query needs at least one bound.",
+                    ));
+                }
+                if *use_inverted_index {
+                    Box::new(tv::query::InvertedIndexRangeQuery::new(
+                        lower_bound,
+                        upper_bound,
+                    ))
+                } else {
+                    Box::new(tv::query::RangeQuery::new(
+                        lower_bound,
+                        upper_bound,
+                    ))
+                }
+            }
+            QuerySpec::MoreLikeThis {
+                document_fields,
+                options,
+            } => Box::new(
+                options.builder().with_document_fields(
+                    document_fields
+                        .iter()
+                        .map(|(field, values)| {
+                            Ok((
+                                schema_field(schema, field, None)?,
+                                values
+                                    .iter()
+                                    .cloned()
+                                    .map(Into::into)
+                                    .collect(),
+                            ))
+                        })
+                        .collect::<PyResult<_>>()?,
+                ),
+            ),
+        })
+    }
+}
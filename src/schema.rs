@@ -1,12 +1,20 @@
-use crate::to_pyerr;
+use crate::{
+    document::{check_py_values_for_type, Document},
+    get_field, to_pyerr,
+};
 use pyo3::IntoPyObjectExt;
-use pyo3::{basic::CompareOp, prelude::*, types::PyTuple};
+use pyo3::{
+    basic::CompareOp,
+    exceptions,
+    prelude::*,
+    types::{PyDict, PyTuple},
+};
 use serde::{Deserialize, Serialize};
-use tantivy as tv;
+use tantivy::{self as tv, schema::document::OwnedValue as Value};
 
 /// Tantivy's Type
-#[pyclass(frozen, module = "tantivy.tantivy")]
-#[derive(Clone, PartialEq)]
+#[pyclass(frozen, eq, hash, module = "tantivy.tantivy")]
+#[derive(Clone, Debug, Hash, PartialEq)]
 pub(crate) enum FieldType {
     Text,
     Unsigned,
@@ -37,6 +45,195 @@ impl From<FieldType> for tv::schema::Type {
     }
 }
 
+impl From<&tv::schema::FieldType> for FieldType {
+    fn from(field_type: &tv::schema::FieldType) -> FieldType {
+        match field_type {
+            tv::schema::FieldType::Str(_) => FieldType::Text,
+            tv::schema::FieldType::U64(_) => FieldType::Unsigned,
+            tv::schema::FieldType::I64(_) => FieldType::Integer,
+            tv::schema::FieldType::F64(_) => FieldType::Float,
+            tv::schema::FieldType::Bool(_) => FieldType::Boolean,
+            tv::schema::FieldType::Date(_) => FieldType::Date,
+            tv::schema::FieldType::Facet(_) => FieldType::Facet,
+            tv::schema::FieldType::Bytes(_) => FieldType::Bytes,
+            tv::schema::FieldType::JsonObject(_) => FieldType::Json,
+            tv::schema::FieldType::IpAddr(_) => FieldType::IpAddr,
+        }
+    }
+}
+
+/// Description of a single field of a `Schema`.
+///
+/// Options that do not apply to the type of the field, e.g. `tokenizer` for
+/// an integer field, are `None`.
+#[pyclass(frozen, module = "tantivy.tantivy")]
+#[derive(Clone, PartialEq)]
+pub(crate) struct FieldEntry {
+    pub(crate) inner: tv::schema::FieldEntry,
+}
+
+impl FieldEntry {
+    /// Text indexing options of text and JSON fields, if indexed.
+    fn text_indexing(&self) -> Option<&tv::schema::TextFieldIndexing> {
+        match self.inner.field_type() {
+            tv::schema::FieldType::Str(options) => {
+                options.get_indexing_options()
+            }
+            tv::schema::FieldType::JsonObject(options) => {
+                options.get_text_indexing_options()
+            }
+            _ => None,
+        }
+    }
+}
+
+#[pymethods]
+impl FieldEntry {
+    /// The name of the field.
+    #[getter]
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    /// The type of the field.
+    #[getter]
+    fn field_type(&self) -> FieldType {
+        self.inner.field_type().into()
+    }
+
+    /// Whether the original value is kept in the document store.
+    #[getter]
+    fn stored(&self) -> bool {
+        self.inner.is_stored()
+    }
+
+    /// Whether the field is indexed, i.e. searchable.
+    #[getter]
+    fn indexed(&self) -> bool {
+        self.inner.is_indexed()
+    }
+
+    /// Whether the field is a fast field (columnar storage).
+    #[getter]
+    fn fast(&self) -> bool {
+        self.inner.is_fast()
+    }
+
+    /// Whether field norms are recorded for the field.
+    #[getter]
+    fn fieldnorms(&self) -> bool {
+        self.inner.has_fieldnorms()
+    }
+
+    /// Name of the tokenizer used to index a text or JSON field.
+    #[getter]
+    fn tokenizer(&self) -> Option<&str> {
+        self.text_indexing().map(|indexing| indexing.tokenizer())
+    }
+
+    /// Index record option of a text or JSON field: "basic", "freq" or
+    /// "position".
+    #[getter]
+    fn index_option(&self) -> Option<&'static str> {
+        self.text_indexing()
+            .map(|indexing| match indexing.index_option() {
+                tv::schema::IndexRecordOption::Basic => "basic",
+                tv::schema::IndexRecordOption::WithFreqs => "freq",
+                tv::schema::IndexRecordOption::WithFreqsAndPositions => {
+                    "position"
+                }
+            })
+    }
+
+    /// Name of the normalizer applied to a fast text or JSON field.
+    #[getter]
+    fn fast_tokenizer(&self) -> Option<&str> {
+        match self.inner.field_type() {
+            tv::schema::FieldType::Str(options) => {
+                options.get_fast_field_tokenizer_name()
+            }
+            tv::schema::FieldType::JsonObject(options) => {
+                options.get_fast_field_tokenizer_name()
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether dots in the keys of a JSON field are treated as nesting.
+    #[getter]
+    fn expand_dots(&self) -> Option<bool> {
+        match self.inner.field_type() {
+            tv::schema::FieldType::JsonObject(options) => {
+                Some(options.is_expand_dots_enabled())
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether values of another type are coerced into the type of a text,
+    /// numeric or boolean field.
+    #[getter]
+    fn coerce(&self) -> Option<bool> {
+        match self.inner.field_type() {
+            tv::schema::FieldType::Str(options) => {
+                Some(options.should_coerce())
+            }
+            tv::schema::FieldType::U64(options)
+            | tv::schema::FieldType::I64(options)
+            | tv::schema::FieldType::F64(options)
+            | tv::schema::FieldType::Bool(options) => {
+                Some(options.should_coerce())
+            }
+            _ => None,
+        }
+    }
+
+    /// Precision of a date fast field: "seconds", "milliseconds",
+    /// "microseconds" or "nanoseconds".
+    #[getter]
+    fn precision(&self) -> Option<&'static str> {
+        match self.inner.field_type() {
+            tv::schema::FieldType::Date(options) => {
+                Some(match options.get_precision() {
+                    tv::schema::DateTimePrecision::Seconds => "seconds",
+                    tv::schema::DateTimePrecision::Milliseconds => {
+                        "milliseconds"
+                    }
+                    tv::schema::DateTimePrecision::Microseconds => {
+                        "microseconds"
+                    }
+                    tv::schema::DateTimePrecision::Nanoseconds => "nanoseconds",
+                })
+            }
+            _ => None,
+        }
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "FieldEntry(name={:?}, field_type=FieldType.{:?}, stored={}, indexed={}, fast={})",
+            self.inner.name(),
+            FieldType::from(self.inner.field_type()),
+            self.stored(),
+            self.indexed(),
+            self.fast(),
+        )
+    }
+
+    fn __richcmp__(
+        &self,
+        other: &Self,
+        op: CompareOp,
+        py: Python<'_>,
+    ) -> PyResult<Py<PyAny>> {
+        match op {
+            CompareOp::Eq => (self == other).into_py_any(py),
+            CompareOp::Ne => (self != other).into_py_any(py),
+            _ => Ok(py.NotImplemented()),
+        }
+    }
+}
+
 /// Tantivy schema.
 ///
 /// The schema is very strict. To build the schema the `SchemaBuilder` class is
@@ -49,6 +246,119 @@ pub(crate) struct Schema {
 
 #[pymethods]
 impl Schema {
+    /// Returns the description of every field, in the order they were added
+    /// to the schema.
+    fn fields(&self) -> Vec<FieldEntry> {
+        self.inner
+            .fields()
+            .map(|(_, entry)| FieldEntry {
+                inner: entry.clone(),
+            })
+            .collect()
+    }
+
+    /// Returns the description of the given field.
+    ///
+    /// Args:
+    ///     field_name (str): The name of the field.
+    ///
+    /// Raises ValueError if the field is not defined in the schema.
+    fn get_field_entry(&self, field_name: &str) -> PyResult<FieldEntry> {
+        let field = get_field(&self.inner, field_name)?;
+        Ok(FieldEntry {
+            inner: self.inner.get_field_entry(field).clone(),
+        })
+    }
+
+    /// Checks a document against the schema, without indexing it.
+    ///
+    /// All the problems of the document are reported rather than only the
+    /// first one. The kind of a problem is one of:
+    ///
+    /// - "unknown_field": the field is not defined in the schema.
+    /// - "wrong_type": the type of a value is not accepted by the field.
+    /// - "invalid_value": the type of a value is accepted by the field but
+    ///   the value itself is not, e.g. a malformed IP address.
+    /// - "multiple_values": a field listed in `single_valued` holds more
+    ///   than one value.
+    ///
+    /// Args:
+    ///     document (Document | Dict[str, Any]): The document to check. A
+    ///         dict is checked the way `Document.from_dict()` converts it
+    ///         with this schema.
+    ///     single_valued (List[str], optional): Names of the fields that must
+    ///         not hold more than one value. Tantivy accepts several values
+    ///         for any field, so this is left to the caller.
+    ///
+    /// Returns a list of `ValidationProblem`, which is empty if the document
+    /// is valid.
+    #[pyo3(signature = (document, single_valued = None))]
+    fn validate(
+        &self,
+        document: &Bound<PyAny>,
+        single_valued: Option<Vec<String>>,
+    ) -> PyResult<Vec<ValidationProblem>> {
+        let single_valued = single_valued.unwrap_or_default();
+        let mut problems = Vec::new();
+        if let Ok(document) = document.downcast::<Document>() {
+            for (field_name, values) in document.borrow().field_values.iter() {
+                let Some(field_type) =
+                    self.field_type_or_problem(field_name, &mut problems)
+                else {
+                    continue;
+                };
+                let value_problems =
+                    check_values_for_type(values, field_type, field_name);
+                push_value_problems(
+                    &mut problems,
+                    field_name,
+                    values.len(),
+                    value_problems,
+                    &single_valued,
+                );
+            }
+        } else if let Ok(dict) = document.downcast::<PyDict>() {
+            for (key, value) in dict.iter() {
+                let field_name: String = key.extract()?;
+                let Some(field_type) =
+                    self.field_type_or_problem(&field_name, &mut problems)
+                else {
+                    continue;
+                };
+                let (num_values, value_problems) =
+                    check_py_values_for_type(&value, field_type, &field_name)?;
+                push_value_problems(
+                    &mut problems,
+                    &field_name,
+                    num_values,
+                    value_problems,
+                    &single_valued,
+                );
+            }
+        } else {
+            return Err(exceptions::PyValueError::new_err(
+                "Expected a Document or a dict.",
+            ));
+        }
+        Ok(problems)
+    }
+
+    /// Serializes the schema to JSON, in the format used by tantivy in the
+    /// `meta.json` file of an index.
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.inner).map_err(to_pyerr)
+    }
+
+    /// Creates a schema from its JSON representation, as produced by
+    /// `to_json()`.
+    ///
+    /// Raises ValueError if the JSON is not a valid schema.
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Schema> {
+        let inner = serde_json::from_str(json).map_err(to_pyerr)?;
+        Ok(Schema { inner })
+    }
+
     fn __richcmp__(
         &self,
         other: &Self,
@@ -81,3 +391,128 @@ impl Schema {
         )
     }
 }
+
+impl Schema {
+    /// Returns the type of a field, or records an "unknown_field" problem
+    /// if the field is not defined in the schema.
+    fn field_type_or_problem(
+        &self,
+        field_name: &str,
+        problems: &mut Vec<ValidationProblem>,
+    ) -> Option<&tv::schema::FieldType> {
+        match self.inner.get_field(field_name) {
+            Ok(field) => Some(self.inner.get_field_entry(field).field_type()),
+            Err(_) => {
+                problems.push(ValidationProblem::new(
+                    field_name,
+                    "unknown_field",
+                    format!(
+                        "Field `{field_name}` is not defined in the schema."
+                    ),
+                ));
+                None
+            }
+        }
+    }
+}
+
+/// Records the problems found in the values of a field, along with a
+/// "multiple_values" problem if the field must hold a single value.
+fn push_value_problems(
+    problems: &mut Vec<ValidationProblem>,
+    field_name: &str,
+    num_values: usize,
+    value_problems: Vec<(&'static str, String)>,
+    single_valued: &[String],
+) {
+    for (kind, message) in value_problems {
+        problems.push(ValidationProblem::new(field_name, kind, message));
+    }
+    if num_values > 1 && single_valued.iter().any(|name| name == field_name) {
+        problems.push(ValidationProblem::new(
+            field_name,
+            "multiple_values",
+            format!(
+                "Field `{field_name}` must hold a single value, got \
+                 {num_values}."
+            ),
+        ));
+    }
+}
+
+/// A problem found by `Schema.validate()`.
+#[pyclass(frozen, eq, module = "tantivy.tantivy")]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct ValidationProblem {
+    /// The name of the field the problem was found in.
+    #[pyo3(get)]
+    field_name: String,
+    /// One of "unknown_field", "wrong_type", "invalid_value" or
+    /// "multiple_values".
+    #[pyo3(get)]
+    kind: String,
+    /// A description of the problem.
+    #[pyo3(get)]
+    message: String,
+}
+
+impl ValidationProblem {
+    fn new(field_name: &str, kind: &str, message: String) -> Self {
+        ValidationProblem {
+            field_name: field_name.to_string(),
+            kind: kind.to_string(),
+            message,
+        }
+    }
+}
+
+#[pymethods]
+impl ValidationProblem {
+    fn __repr__(&self) -> String {
+        format!(
+            "ValidationProblem(field_name={:?}, kind={:?}, message={:?})",
+            self.field_name, self.kind, self.message
+        )
+    }
+}
+
+/// Checks the values of a `Document` against the type of a field, in the
+/// same way as `check_py_values_for_type()`. The values of a `Document`
+/// created without a schema may not match the field type, e.g. a Python
+/// `int` is always converted to a signed integer.
+pub(crate) fn check_values_for_type(
+    values: &[Value],
+    field_type: &tv::schema::FieldType,
+    field_name: &str,
+) -> Vec<(&'static str, String)> {
+    use tv::schema::Type;
+
+    let tv_type = field_type.value_type();
+    values
+        .iter()
+        .filter(|value| {
+            !matches!(
+                (value, tv_type),
+                (Value::Str(_) | Value::PreTokStr(_), Type::Str)
+                    | (Value::U64(_), Type::U64)
+                    | (Value::I64(_), Type::I64)
+                    | (Value::F64(_), Type::F64)
+                    | (Value::Bool(_), Type::Bool)
+                    | (Value::Date(_), Type::Date)
+                    | (Value::Facet(_), Type::Facet)
+                    | (Value::Bytes(_), Type::Bytes)
+                    | (Value::Object(_), Type::Json)
+                    | (Value::IpAddr(_), Type::IpAddr)
+            )
+        })
+        .map(|value| {
+            (
+                "wrong_type",
+                format!(
+                    "Expected {tv_type:?} type for field {field_name}, got \
+                     {value:?}"
+                ),
+            )
+        })
+        .collect()
+}
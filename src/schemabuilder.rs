@@ -1,11 +1,23 @@
 #![allow(clippy::new_ret_no_self)]
 
-use pyo3::{exceptions, prelude::*};
+use pyo3::{
+    exceptions,
+    prelude::*,
+    types::{PyBool, PyDict, PyInt, PyList, PyTuple, PyType},
+};
 
-use crate::schema::Schema;
-use std::sync::{Arc, RwLock};
+use crate::{
+    document::extract_value,
+    facet::Facet,
+    schema::{FieldType, Schema},
+};
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
 use tantivy::schema::{
-    self, BytesOptions, DateOptions, IpAddrOptions, JsonObjectOptions, INDEXED,
+    self, document::OwnedValue as Value, BytesOptions, DateOptions,
+    DateTimePrecision, FacetOptions, IpAddrOptions, JsonObjectOptions,
 };
 
 /// Tantivy has a very strict schema.
@@ -32,6 +44,7 @@ pub(crate) struct SchemaBuilder {
 const NO_TOKENIZER_NAME: &str = "raw";
 const TOKENIZER: &str = "default";
 const RECORD: &str = "position";
+const PRECISION: &str = "seconds";
 
 #[pymethods]
 impl SchemaBuilder {
@@ -71,6 +84,17 @@ impl SchemaBuilder {
     ///         document id and the term frequency, while the 'position' option
     ///         records the document id, term frequency and the positions of
     ///         the term occurrences in the document.
+    ///     indexed (bool, optional): If false, the field is not searchable
+    ///         and `tokenizer_name`, `index_option` and `fieldnorms` are
+    ///         ignored. This is useful for stored-only fields. Defaults to
+    ///         True.
+    ///     fieldnorms (bool, optional): If true, the number of tokens of the
+    ///         field is recorded for each document, which BM25 uses to score
+    ///         matches in shorter fields higher. Defaults to True.
+    ///     fast_tokenizer_name (str, optional): The name of the normalizer
+    ///         applied to the values of a fast field, when it should differ
+    ///         from `tokenizer_name`. Defaults to `tokenizer_name`, or no
+    ///         normalization when it is 'raw'.
     ///
     /// Returns the associated field handle.
     /// Raises a ValueError if there was an error with the field creation.
@@ -79,8 +103,12 @@ impl SchemaBuilder {
         stored = false,
         fast = false,
         tokenizer_name = TOKENIZER,
-        index_option = RECORD
+        index_option = RECORD,
+        indexed = true,
+        fieldnorms = true,
+        fast_tokenizer_name = None
     ))]
+    #[allow(clippy::too_many_arguments)]
     fn add_text_field(
         &mut self,
         name: &str,
@@ -88,6 +116,9 @@ impl SchemaBuilder {
         fast: bool,
         tokenizer_name: &str,
         index_option: &str,
+        indexed: bool,
+        fieldnorms: bool,
+        fast_tokenizer_name: Option<&str>,
     ) -> PyResult<Self> {
         let builder = &mut self.builder;
         let options = SchemaBuilder::build_text_option(
@@ -95,6 +126,9 @@ impl SchemaBuilder {
             fast,
             tokenizer_name,
             index_option,
+            indexed,
+            fieldnorms,
+            fast_tokenizer_name,
         )?;
 
         if let Some(builder) = builder.write().unwrap().as_mut() {
@@ -119,20 +153,36 @@ impl SchemaBuilder {
     ///         fast field is a column-oriented fashion storage for tantivy.
     ///         It is designed for the fast random access of some document
     ///         fields given a document id.
+    ///     fieldnorms (bool, optional): If true and the field is indexed,
+    ///         field norms are recorded for the field. Defaults to False.
+    ///     coerce (bool, optional): If true, values of another type are
+    ///         converted to the type of the field when possible, e.g. the
+    ///         string "42" in a JSON document. Defaults to False.
     ///
     /// Returns the associated field handle.
     /// Raises a ValueError if there was an error with the field creation.
-    #[pyo3(signature = (name, stored = false, indexed = false, fast = false))]
+    #[pyo3(signature = (
+        name,
+        stored = false,
+        indexed = false,
+        fast = false,
+        fieldnorms = false,
+        coerce = false
+    ))]
     fn add_integer_field(
         &mut self,
         name: &str,
         stored: bool,
         indexed: bool,
         fast: bool,
+        fieldnorms: bool,
+        coerce: bool,
     ) -> PyResult<Self> {
         let builder = &mut self.builder;
 
-        let opts = SchemaBuilder::build_numeric_option(stored, indexed, fast)?;
+        let opts = SchemaBuilder::build_numeric_option(
+            stored, indexed, fast, fieldnorms, coerce,
+        )?;
 
         if let Some(builder) = builder.write().unwrap().as_mut() {
             builder.add_i64_field(name, opts);
@@ -156,20 +206,36 @@ impl SchemaBuilder {
     ///         fast field is a column-oriented fashion storage for tantivy.
     ///         It is designed for the fast random access of some document
     ///         fields given a document id.
+    ///     fieldnorms (bool, optional): If true and the field is indexed,
+    ///         field norms are recorded for the field. Defaults to False.
+    ///     coerce (bool, optional): If true, values of another type are
+    ///         converted to the type of the field when possible, e.g. the
+    ///         string "42" in a JSON document. Defaults to False.
     ///
     /// Returns the associated field handle.
     /// Raises a ValueError if there was an error with the field creation.
-    #[pyo3(signature = (name, stored = false, indexed = false, fast = false))]
+    #[pyo3(signature = (
+        name,
+        stored = false,
+        indexed = false,
+        fast = false,
+        fieldnorms = false,
+        coerce = false
+    ))]
     fn add_float_field(
         &mut self,
         name: &str,
         stored: bool,
         indexed: bool,
         fast: bool,
+        fieldnorms: bool,
+        coerce: bool,
     ) -> PyResult<Self> {
         let builder = &mut self.builder;
 
-        let opts = SchemaBuilder::build_numeric_option(stored, indexed, fast)?;
+        let opts = SchemaBuilder::build_numeric_option(
+            stored, indexed, fast, fieldnorms, coerce,
+        )?;
 
         if let Some(builder) = builder.write().unwrap().as_mut() {
             builder.add_f64_field(name, opts);
@@ -193,20 +259,36 @@ impl SchemaBuilder {
     ///         fast field is a column-oriented fashion storage for tantivy.
     ///         It is designed for the fast random access of some document
     ///         fields given a document id.
+    ///     fieldnorms (bool, optional): If true and the field is indexed,
+    ///         field norms are recorded for the field. Defaults to False.
+    ///     coerce (bool, optional): If true, values of another type are
+    ///         converted to the type of the field when possible, e.g. the
+    ///         string "42" in a JSON document. Defaults to False.
     ///
     /// Returns the associated field handle.
     /// Raises a ValueError if there was an error with the field creation.
-    #[pyo3(signature = (name, stored = false, indexed = false, fast = false))]
+    #[pyo3(signature = (
+        name,
+        stored = false,
+        indexed = false,
+        fast = false,
+        fieldnorms = false,
+        coerce = false
+    ))]
     fn add_unsigned_field(
         &mut self,
         name: &str,
         stored: bool,
         indexed: bool,
         fast: bool,
+        fieldnorms: bool,
+        coerce: bool,
     ) -> PyResult<Self> {
         let builder = &mut self.builder;
 
-        let opts = SchemaBuilder::build_numeric_option(stored, indexed, fast)?;
+        let opts = SchemaBuilder::build_numeric_option(
+            stored, indexed, fast, fieldnorms, coerce,
+        )?;
 
         if let Some(builder) = builder.write().unwrap().as_mut() {
             builder.add_u64_field(name, opts);
@@ -230,20 +312,36 @@ impl SchemaBuilder {
     ///         fast field is a column-oriented fashion storage for tantivy.
     ///         It is designed for the fast random access of some document
     ///         fields given a document id.
+    ///     fieldnorms (bool, optional): If true and the field is indexed,
+    ///         field norms are recorded for the field. Defaults to False.
+    ///     coerce (bool, optional): If true, values of another type are
+    ///         converted to the type of the field when possible, e.g. the
+    ///         string "42" in a JSON document. Defaults to False.
     ///
     /// Returns the associated field handle.
     /// Raises a ValueError if there was an error with the field creation.
-    #[pyo3(signature = (name, stored = false, indexed = false, fast = false))]
+    #[pyo3(signature = (
+        name,
+        stored = false,
+        indexed = false,
+        fast = false,
+        fieldnorms = false,
+        coerce = false
+    ))]
     fn add_boolean_field(
         &mut self,
         name: &str,
         stored: bool,
         indexed: bool,
         fast: bool,
+        fieldnorms: bool,
+        coerce: bool,
     ) -> PyResult<Self> {
         let builder = &mut self.builder;
 
-        let opts = SchemaBuilder::build_numeric_option(stored, indexed, fast)?;
+        let opts = SchemaBuilder::build_numeric_option(
+            stored, indexed, fast, fieldnorms, coerce,
+        )?;
 
         if let Some(builder) = builder.write().unwrap().as_mut() {
             builder.add_bool_field(name, opts);
@@ -267,20 +365,40 @@ impl SchemaBuilder {
     ///         field is a column-oriented fashion storage for tantivy. It is
     ///         designed for the fast random access of some document fields
     ///         given a document id.
+    ///     precision (str, optional): The precision dates are truncated to in
+    ///         the fast field. Can be one of 'seconds', 'milliseconds',
+    ///         'microseconds' or 'nanoseconds'. Defaults to 'seconds'. The
+    ///         stored value always keeps the full precision.
     ///
     /// Returns the associated field handle.
     /// Raises a ValueError if there was an error with the field creation.
-    #[pyo3(signature = (name, stored = false, indexed = false, fast = false))]
+    #[pyo3(signature = (
+        name,
+        stored = false,
+        indexed = false,
+        fast = false,
+        precision = PRECISION
+    ))]
     fn add_date_field(
         &mut self,
         name: &str,
         stored: bool,
         indexed: bool,
         fast: bool,
+        precision: &str,
     ) -> PyResult<Self> {
         let builder = &mut self.builder;
 
-        let mut opts = DateOptions::default();
+        let precision = match precision {
+            "seconds" => DateTimePrecision::Seconds,
+            "milliseconds" => DateTimePrecision::Milliseconds,
+            "microseconds" => DateTimePrecision::Microseconds,
+            "nanoseconds" => DateTimePrecision::Nanoseconds,
+            _ => return Err(exceptions::PyValueError::new_err(
+                "Invalid precision, valid choices are: 'seconds', 'milliseconds', 'microseconds' and 'nanoseconds'"
+            ))
+        };
+        let mut opts = DateOptions::default().set_precision(precision);
         if stored {
             opts = opts.set_stored();
         }
@@ -358,6 +476,9 @@ impl SchemaBuilder {
             fast,
             tokenizer_name,
             index_option,
+            true,
+            true,
+            None,
         )?;
 
         let mut json_options: JsonObjectOptions = text_options.into();
@@ -377,13 +498,41 @@ impl SchemaBuilder {
     }
 
     /// Add a Facet field to the schema.
+    ///
+    /// Facet fields are always indexed, and always stored as a fast field,
+    /// which makes them usable in aggregations.
+    ///
     /// Args:
     ///     name (str): The name of the field.
-    fn add_facet_field(&mut self, name: &str) -> PyResult<Self> {
+    ///     stored (bool, optional): If true sets the field as stored, the
+    ///         facets can be later restored from a Searcher as `Facet`
+    ///         objects. Defaults to False.
+    ///     fast (bool, optional): Only True is accepted, since tantivy
+    ///         always stores facets as a fast field. Defaults to True.
+    ///
+    /// Raises a ValueError if there was an error with the field creation.
+    #[pyo3(signature = (name, stored = false, fast = true))]
+    fn add_facet_field(
+        &mut self,
+        name: &str,
+        stored: bool,
+        fast: bool,
+    ) -> PyResult<Self> {
         let builder = &mut self.builder;
 
+        if !fast {
+            return Err(exceptions::PyValueError::new_err(
+                "Facet fields are always fast fields, `fast` cannot be False.",
+            ));
+        }
+        let opts = if stored {
+            FacetOptions::default().set_stored()
+        } else {
+            FacetOptions::default()
+        };
+
         if let Some(builder) = builder.write().unwrap().as_mut() {
-            builder.add_facet_field(name, INDEXED);
+            builder.add_facet_field(name, opts);
         } else {
             return Err(exceptions::PyValueError::new_err(
                 "Schema builder object isn't valid anymore.",
@@ -487,11 +636,227 @@ impl SchemaBuilder {
         Ok(self.clone())
     }
 
+    /// Propose a schema from sample documents.
+    ///
+    /// Every key of the sample dictionaries becomes a field, in the order
+    /// in which the keys are first seen. The type of a field is deduced
+    /// from its values: `str` gives a text field, `bool` a boolean field,
+    /// `int` an integer field (unsigned if a value doesn't fit in a signed
+    /// 64 bits integer), `float` a float field, `datetime` and `date` a date
+    /// field, `Facet` a facet field, `bytes` a bytes field, `dict` a JSON
+    /// field and the `ipaddress` types an IP address field. Lists are
+    /// treated as multi-valued fields and `None` values are ignored. A field
+    /// mixing integers and floats becomes a float field, while a field
+    /// mixing integers that fit in a signed 64 bits integer with integers
+    /// that don't is an error.
+    ///
+    /// All the inferred fields are stored, and the non-text fields are
+    /// also indexed.
+    ///
+    /// Args:
+    ///     samples (Iterable[Dict[str, Any]]): The sample documents.
+    ///     overrides (Dict[str, Dict[str, Any]], optional): Options for
+    ///         some of the fields, passed as keyword arguments to the
+    ///         matching `add_*_field` method. The special "type" key takes
+    ///         a `FieldType` and replaces the inferred type, it is required
+    ///         for fields that don't appear in the samples.
+    ///
+    /// Returns a SchemaBuilder that can be extended further before being
+    /// built.
+    /// Raises a ValueError if a field has values of incompatible types or
+    /// if its type cannot be inferred.
+    #[staticmethod]
+    #[pyo3(signature = (samples, overrides = None))]
+    fn infer(
+        py: Python,
+        samples: &Bound<PyAny>,
+        overrides: Option<&Bound<PyDict>>,
+    ) -> PyResult<Self> {
+        let mut fields: Vec<(String, Option<FieldType>)> = Vec::new();
+        // The positions of the fields in `fields`, by name.
+        let mut positions: HashMap<String, usize> = HashMap::new();
+        for sample in samples.try_iter()? {
+            let sample = sample?;
+            let sample = sample.downcast::<PyDict>().map_err(|_| {
+                exceptions::PyValueError::new_err(
+                    "Samples must be dictionaries.",
+                )
+            })?;
+            for (key, value) in sample.iter() {
+                let name: String = key.extract()?;
+                let position = match positions.get(&name) {
+                    Some(position) => *position,
+                    None => {
+                        fields.push((name.clone(), None));
+                        positions.insert(name.clone(), fields.len() - 1);
+                        fields.len() - 1
+                    }
+                };
+                // The values of a field whose type is overridden are not
+                // inspected, they may well be of incompatible types.
+                if overridden_type(overrides, &name)?.is_some() {
+                    continue;
+                }
+                let values = if value.is_instance_of::<PyList>()
+                    || value.is_instance_of::<PyTuple>()
+                {
+                    value.try_iter()?.collect::<PyResult<Vec<_>>>()?
+                } else {
+                    vec![value]
+                };
+                for value in values {
+                    let Some(value_type) = infer_field_type(&name, &value)?
+                    else {
+                        continue;
+                    };
+                    let field_type = &mut fields[position].1;
+                    *field_type = Some(match field_type.take() {
+                        Some(field_type) => {
+                            merge_field_types(&name, field_type, value_type)?
+                        }
+                        None => value_type,
+                    });
+                }
+            }
+        }
+
+        let empty = PyDict::new(py);
+        let builder = Bound::new(py, SchemaBuilder::new())?;
+        for (name, inferred) in fields.iter() {
+            let options = match overrides {
+                Some(overrides) => overrides
+                    .get_item(name)?
+                    .map(|options| options.downcast_into::<PyDict>())
+                    .transpose()?,
+                None => None,
+            };
+            let options = options.unwrap_or_else(|| empty.clone());
+            let field_type = match options.get_item("type")? {
+                Some(field_type) => field_type.extract()?,
+                None => inferred.clone().ok_or_else(|| {
+                    exceptions::PyValueError::new_err(format!(
+                        "Cannot infer the type of field `{name}` since all \
+                         its sample values are None, set it in `overrides`."
+                    ))
+                })?,
+            };
+            add_field_of_type(&builder, name, &field_type, &options)?;
+        }
+
+        if let Some(overrides) = overrides {
+            for (name, options) in overrides.iter() {
+                let name: String = name.extract()?;
+                if positions.contains_key(&name) {
+                    continue;
+                }
+                let options = options.downcast_into::<PyDict>()?;
+                let field_type = options
+                    .get_item("type")?
+                    .ok_or_else(|| {
+                        exceptions::PyValueError::new_err(format!(
+                            "Field `{name}` doesn't appear in the samples, \
+                             its `type` must be given in `overrides`."
+                        ))
+                    })?
+                    .extract()?;
+                add_field_of_type(&builder, &name, &field_type, &options)?;
+            }
+        }
+
+        let builder = builder.borrow().clone();
+        Ok(builder)
+    }
+
+    /// Create a schema from the type annotations of a dataclass or of a
+    /// TypedDict.
+    ///
+    /// Annotations are mapped to fields as follows: `str` to text, `bool`
+    /// to boolean, `int` to integer, `float` to float, `datetime` and
+    /// `date` to date, `bytes` to bytes, `Facet` to facet, `dict` and
+    /// `Mapping` to JSON and the `ipaddress` types to IP address fields.
+    /// `Optional[T]` and `List[T]` are mapped like `T`.
+    ///
+    /// The options of a field are read from a dict given to
+    /// `typing.Annotated`, or from the "tantivy" key of the metadata of a
+    /// dataclass field, and are passed as keyword arguments to the
+    /// matching `add_*_field` method. The special "type" key takes a
+    /// `FieldType` and replaces the type deduced from the annotation.
+    /// Without options, the fields are stored and the non-text fields are
+    /// also indexed.
+    ///
+    /// Args:
+    ///     cls (type): The dataclass or TypedDict.
+    ///
+    /// Returns a SchemaBuilder that can be extended further before being
+    /// built.
+    /// Raises a ValueError if an annotation cannot be mapped to a field
+    /// type.
+    #[staticmethod]
+    fn from_dataclass(py: Python, cls: &Bound<PyAny>) -> PyResult<Self> {
+        let typing = py.import("typing")?;
+        let kwargs = PyDict::new(py);
+        kwargs.set_item("include_extras", true)?;
+        let hints = typing
+            .call_method("get_type_hints", (cls,), Some(&kwargs))?
+            .downcast_into::<PyDict>()?;
+
+        let dataclasses = py.import("dataclasses")?;
+        let mut fields = Vec::new();
+        if dataclasses
+            .call_method1("is_dataclass", (cls,))?
+            .is_truthy()?
+        {
+            // Iterate over the dataclass fields rather than the type hints
+            // to skip the `ClassVar` annotations.
+            for field in
+                dataclasses.call_method1("fields", (cls,))?.try_iter()?
+            {
+                let field = field?;
+                let name: String = field.getattr("name")?.extract()?;
+                let metadata = field
+                    .getattr("metadata")?
+                    .call_method1("get", ("tantivy",))?;
+                fields.push((name, Some(metadata)));
+            }
+        } else {
+            for name in hints.keys() {
+                fields.push((name.extract()?, None));
+            }
+        }
+
+        let builder = Bound::new(py, SchemaBuilder::new())?;
+        for (name, metadata) in fields {
+            let hint = hints.get_item(&name)?.ok_or_else(|| {
+                exceptions::PyValueError::new_err(format!(
+                    "Field `{name}` has no type annotation."
+                ))
+            })?;
+            let options = PyDict::new(py);
+            let annotated = annotation_field_type(&typing, &hint, &options);
+            if let Some(metadata) = metadata.filter(|m| !m.is_none()) {
+                options.update(metadata.downcast::<PyDict>()?.as_mapping())?;
+            }
+            let field_type = match options.get_item("type")? {
+                Some(field_type) => field_type.extract()?,
+                None => annotated?.ok_or_else(|| {
+                    exceptions::PyValueError::new_err(format!(
+                        "Cannot map the annotation {hint} of field `{name}` \
+                         to a field type."
+                    ))
+                })?,
+            };
+            add_field_of_type(&builder, &name, &field_type, &options)?;
+        }
+
+        let builder = builder.borrow().clone();
+        Ok(builder)
+    }
+
     /// Finalize the creation of a Schema.
     ///
     /// Returns a Schema object. After this is called the SchemaBuilder cannot
     /// be used anymore.
-    fn build(&mut self) -> PyResult<Schema> {
+    pub(crate) fn build(&mut self) -> PyResult<Schema> {
         let builder = self.builder.write().unwrap().take();
         if let Some(builder) = builder {
             let schema = builder.build();
@@ -509,19 +874,31 @@ impl SchemaBuilder {
         stored: bool,
         indexed: bool,
         fast: bool,
+        fieldnorms: bool,
+        coerce: bool,
     ) -> PyResult<schema::NumericOptions> {
         let opts = schema::NumericOptions::default();
         let opts = if stored { opts.set_stored() } else { opts };
         let opts = if indexed { opts.set_indexed() } else { opts };
         let opts = if fast { opts.set_fast() } else { opts };
+        let opts = if fieldnorms {
+            opts.set_fieldnorm()
+        } else {
+            opts
+        };
+        let opts = if coerce { opts.set_coerce() } else { opts };
         Ok(opts)
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn build_text_option(
         stored: bool,
         fast: bool,
         tokenizer_name: &str,
         index_option: &str,
+        indexed: bool,
+        fieldnorms: bool,
+        fast_tokenizer_name: Option<&str>,
     ) -> PyResult<schema::TextOptions> {
         let index_option = match index_option {
             "position" => schema::IndexRecordOption::WithFreqsAndPositions,
@@ -532,12 +909,16 @@ impl SchemaBuilder {
             ))
         };
 
-        let indexing = schema::TextFieldIndexing::default()
-            .set_tokenizer(tokenizer_name)
-            .set_index_option(index_option);
-
-        let options =
-            schema::TextOptions::default().set_indexing_options(indexing);
+        let options = schema::TextOptions::default();
+        let options = if indexed {
+            let indexing = schema::TextFieldIndexing::default()
+                .set_tokenizer(tokenizer_name)
+                .set_index_option(index_option)
+                .set_fieldnorms(fieldnorms);
+            options.set_indexing_options(indexing)
+        } else {
+            options
+        };
         let options = if stored {
             options.set_stored()
         } else {
@@ -545,8 +926,10 @@ impl SchemaBuilder {
         };
 
         let options = if fast {
-            let text_tokenizer = if tokenizer_name != NO_TOKENIZER_NAME {
-                Some(tokenizer_name)
+            let fast_tokenizer_name =
+                fast_tokenizer_name.unwrap_or(tokenizer_name);
+            let text_tokenizer = if fast_tokenizer_name != NO_TOKENIZER_NAME {
+                Some(fast_tokenizer_name)
             } else {
                 None
             };
@@ -558,3 +941,216 @@ impl SchemaBuilder {
         Ok(options)
     }
 }
+
+/// Adds a field by calling the `add_*_field` method matching its type, so
+/// that the options are validated in the same way. The fields are stored
+/// and the non-text fields indexed unless `options` says otherwise.
+fn add_field_of_type(
+    builder: &Bound<SchemaBuilder>,
+    name: &str,
+    field_type: &FieldType,
+    options: &Bound<PyDict>,
+) -> PyResult<()> {
+    let kwargs = PyDict::new(builder.py());
+    kwargs.set_item("stored", true)?;
+    let method = match field_type {
+        FieldType::Text => "add_text_field",
+        FieldType::Json => "add_json_field",
+        FieldType::Facet => "add_facet_field",
+        FieldType::Unsigned => "add_unsigned_field",
+        FieldType::Integer => "add_integer_field",
+        FieldType::Float => "add_float_field",
+        FieldType::Boolean => "add_boolean_field",
+        FieldType::Date => "add_date_field",
+        FieldType::Bytes => "add_bytes_field",
+        FieldType::IpAddr => "add_ip_addr_field",
+    };
+    if !matches!(
+        field_type,
+        FieldType::Text | FieldType::Json | FieldType::Facet
+    ) {
+        kwargs.set_item("indexed", true)?;
+    }
+    kwargs.update(options.as_mapping())?;
+    if kwargs.contains("type")? {
+        kwargs.del_item("type")?;
+    }
+    builder.call_method(method, (name,), Some(&kwargs))?;
+    Ok(())
+}
+
+/// Returns the "type" set for a field in the `infer()` overrides.
+fn overridden_type(
+    overrides: Option<&Bound<PyDict>>,
+    name: &str,
+) -> PyResult<Option<FieldType>> {
+    let Some(options) =
+        overrides.map(|o| o.get_item(name)).transpose()?.flatten()
+    else {
+        return Ok(None);
+    };
+    options
+        .downcast_into::<PyDict>()?
+        .get_item("type")?
+        .map(|field_type| field_type.extract())
+        .transpose()
+}
+
+/// Returns the field type of a sample value, or None for a None value.
+fn infer_field_type(
+    name: &str,
+    value: &Bound<PyAny>,
+) -> PyResult<Option<FieldType>> {
+    if value.is_none() {
+        return Ok(None);
+    }
+    if value.is_instance_of::<PyInt>()
+        && !value.is_instance_of::<PyBool>()
+        && value.extract::<i64>().is_err()
+        && value.extract::<u64>().is_ok()
+    {
+        return Ok(Some(FieldType::Unsigned));
+    }
+    let field_type = match extract_value(value) {
+        Ok(Value::Str(_)) => FieldType::Text,
+        Ok(Value::Bool(_)) => FieldType::Boolean,
+        Ok(Value::I64(_)) => FieldType::Integer,
+        Ok(Value::F64(_)) => FieldType::Float,
+        Ok(Value::Date(_)) => FieldType::Date,
+        Ok(Value::Facet(_)) => FieldType::Facet,
+        Ok(Value::Bytes(_)) => FieldType::Bytes,
+        Ok(Value::Object(_)) => FieldType::Json,
+        Ok(Value::IpAddr(_)) => FieldType::IpAddr,
+        _ => {
+            return Err(exceptions::PyValueError::new_err(format!(
+                "Cannot infer the type of field `{name}` from the value \
+                 {value}, set it in `overrides`."
+            )))
+        }
+    };
+    Ok(Some(field_type))
+}
+
+/// Combines the types seen for the same field, integers are widened to
+/// floats when a float is seen.
+///
+/// Unsigned is only inferred for the integers that do not fit in an i64, so
+/// they cannot be mixed with the other integers without losing precision.
+fn merge_field_types(
+    name: &str,
+    left: FieldType,
+    right: FieldType,
+) -> PyResult<FieldType> {
+    use FieldType::*;
+    match (left, right) {
+        (left, right) if left == right => Ok(left),
+        (Integer | Unsigned, Float) | (Float, Integer | Unsigned) => Ok(Float),
+        (Integer, Unsigned) | (Unsigned, Integer) => {
+            Err(exceptions::PyValueError::new_err(format!(
+                "Field `{name}` mixes integers with integers that do not fit \
+                 in a signed 64 bits integer in the samples, set its type in \
+                 `overrides`."
+            )))
+        }
+        (left, right) => Err(exceptions::PyValueError::new_err(format!(
+            "Field `{name}` has values of incompatible types {left:?} and \
+             {right:?} in the samples, set its type in `overrides`."
+        ))),
+    }
+}
+
+/// Returns the field type matching a type annotation, unwrapping
+/// `Annotated`, `Optional`, `Required`, `NotRequired` and collection
+/// types. The dicts given to `Annotated` are merged into `options`.
+fn annotation_field_type(
+    typing: &Bound<PyModule>,
+    hint: &Bound<PyAny>,
+    options: &Bound<PyDict>,
+) -> PyResult<Option<FieldType>> {
+    let py = typing.py();
+    let builtins = py.import("builtins")?;
+    let abc = py.import("collections.abc")?;
+    let types = py.import("types")?;
+
+    let mut hint = hint.clone();
+    loop {
+        let origin = typing.call_method1("get_origin", (&hint,))?;
+        let args = typing
+            .call_method1("get_args", (&hint,))?
+            .downcast_into::<PyTuple>()?;
+        if origin.is_none() {
+            break;
+        }
+        if origin.is(&typing.getattr("Annotated")?) {
+            for extra in hint.getattr("__metadata__")?.try_iter()? {
+                if let Ok(extra) = extra?.downcast::<PyDict>() {
+                    options.update(extra.as_mapping())?;
+                }
+            }
+        } else if origin.is(&typing.getattr("Union")?)
+            || origin.is(&types.getattr("UnionType")?)
+        {
+            let none_type = py.None().into_bound(py).get_type();
+            let args: Vec<_> =
+                args.iter().filter(|arg| !arg.is(&none_type)).collect();
+            if args.len() != 1 {
+                return Ok(None);
+            }
+            hint = args[0].clone();
+            continue;
+        } else {
+            let is_wrapper = |module: &Bound<PyModule>, names: &[&str]| {
+                names.iter().any(|name| {
+                    module.getattr(*name).is_ok_and(|t| origin.is(&t))
+                })
+            };
+            if !is_wrapper(&builtins, &["list", "tuple", "set", "frozenset"])
+                && !is_wrapper(&abc, &["Sequence", "Iterable", "Collection"])
+                && !is_wrapper(typing, &["Required", "NotRequired"])
+            {
+                hint = origin;
+                break;
+            }
+        }
+        if args.is_empty() {
+            return Ok(None);
+        }
+        hint = args.get_item(0)?;
+    }
+
+    let Ok(hint) = hint.downcast::<PyType>() else {
+        return Ok(None);
+    };
+    let is_subclass =
+        |module: &Bound<PyModule>, name: &str| -> PyResult<bool> {
+            hint.is_subclass(&module.getattr(name)?)
+        };
+    let datetime = py.import("datetime")?;
+    let ipaddress = py.import("ipaddress")?;
+    let field_type = if is_subclass(&builtins, "bool")? {
+        FieldType::Boolean
+    } else if is_subclass(&builtins, "int")? {
+        FieldType::Integer
+    } else if is_subclass(&builtins, "float")? {
+        FieldType::Float
+    } else if is_subclass(&builtins, "str")? {
+        FieldType::Text
+    } else if is_subclass(&builtins, "bytes")?
+        || is_subclass(&builtins, "bytearray")?
+    {
+        FieldType::Bytes
+    } else if is_subclass(&datetime, "date")? {
+        FieldType::Date
+    } else if hint.is_subclass_of::<Facet>()? {
+        FieldType::Facet
+    } else if is_subclass(&builtins, "dict")? || is_subclass(&abc, "Mapping")? {
+        FieldType::Json
+    } else if is_subclass(&ipaddress, "IPv4Address")?
+        || is_subclass(&ipaddress, "IPv6Address")?
+    {
+        FieldType::IpAddr
+    } else {
+        return Ok(None);
+    };
+    Ok(Some(field_type))
+}
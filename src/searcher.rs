@@ -6,13 +6,14 @@ use pyo3::IntoPyObjectExt;
 use pyo3::{basic::CompareOp, exceptions::PyValueError, prelude::*};
 use pythonize::{depythonize, pythonize};
 use serde::{Deserialize, Serialize};
-use std::cmp::Reverse;
+use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, HashMap};
 use tantivy as tv;
-use tantivy::aggregation::AggregationCollector;
+use tantivy::aggregation::{agg_req, agg_result, AggregationCollector, Key};
 use tantivy::collector::{
     Collector, Count, MultiCollector, SegmentCollector, TopDocs,
 };
+use tantivy::query::Bm25StatisticsProvider;
 use tantivy::schema::{IndexRecordOption, Type};
 use tantivy::TantivyDocument;
 use tantivy::{DocId, DocSet, Score, SegmentOrdinal, TERMINATED};
@@ -283,6 +284,92 @@ impl SearchResult {
     }
 }
 
+/// Rewrites the bucket keys of terms aggregations over facet fields from
+/// tantivy's internal encoding (`books\0fiction`) to the facet path
+/// (`/books/fiction`), recursing into sub-aggregations.
+fn decode_facet_keys(
+    schema: &tv::schema::Schema,
+    aggs: &agg_req::Aggregations,
+    results: &mut agg_result::AggregationResults,
+) {
+    fn entries_mut<T>(
+        entries: &mut agg_result::BucketEntries<T>,
+    ) -> Box<dyn Iterator<Item = &mut T> + '_> {
+        match entries {
+            agg_result::BucketEntries::Vec(vec) => Box::new(vec.iter_mut()),
+            agg_result::BucketEntries::HashMap(map) => {
+                Box::new(map.values_mut())
+            }
+        }
+    }
+
+    for (name, agg) in aggs {
+        let Some(agg_result::AggregationResult::BucketResult(bucket_result)) =
+            results.0.get_mut(name)
+        else {
+            continue;
+        };
+        let sub_aggs = &agg.sub_aggregation;
+        match bucket_result {
+            agg_result::BucketResult::Terms { buckets, .. } => {
+                let is_facet = match &agg.agg {
+                    agg_req::AggregationVariants::Terms(terms) => schema
+                        .get_field(&terms.field)
+                        .map(|field| {
+                            schema
+                                .get_field_entry(field)
+                                .field_type()
+                                .value_type()
+                                == Type::Facet
+                        })
+                        .unwrap_or(false),
+                    _ => false,
+                };
+                for bucket in buckets {
+                    if let (true, Key::Str(key)) = (is_facet, &mut bucket.key) {
+                        if let Ok(facet) = tv::schema::Facet::from_encoded(
+                            key.clone().into_bytes(),
+                        ) {
+                            *key = facet.to_string();
+                        }
+                    }
+                    decode_facet_keys(
+                        schema,
+                        sub_aggs,
+                        &mut bucket.sub_aggregation,
+                    );
+                }
+            }
+            agg_result::BucketResult::Histogram { buckets } => {
+                for bucket in entries_mut(buckets) {
+                    decode_facet_keys(
+                        schema,
+                        sub_aggs,
+                        &mut bucket.sub_aggregation,
+                    );
+                }
+            }
+            agg_result::BucketResult::Range { buckets } => {
+                for bucket in entries_mut(buckets) {
+                    decode_facet_keys(
+                        schema,
+                        sub_aggs,
+                        &mut bucket.sub_aggregation,
+                    );
+                }
+            }
+            agg_result::BucketResult::Filter(filter) => {
+                decode_facet_keys(
+                    schema,
+                    sub_aggs,
+                    &mut filter.sub_aggregations,
+                );
+            }
+            agg_result::BucketResult::Composite { .. } => {}
+        }
+    }
+}
+
 impl Searcher {
     /// Execute an aggregation from an already-deserialized spec.
     /// Shared by `aggregate()` and `cardinality()` so neither needs to
@@ -295,11 +382,15 @@ impl Searcher {
         aggs: tv::aggregation::agg_req::Aggregations,
     ) -> PyResult<Py<PyDict>> {
         let agg_res = py.detach(move || {
+            let request = aggs.clone();
             let agg_collector =
                 AggregationCollector::from_aggs(aggs, Default::default());
-            self.inner
+            let mut agg_res = self
+                .inner
                 .search(query.get(), &agg_collector)
-                .map_err(to_pyerr)
+                .map_err(to_pyerr)?;
+            decode_facet_keys(self.inner.schema(), &request, &mut agg_res);
+            Ok::<_, PyErr>(agg_res)
         })?;
 
         pythonize(py, &agg_res)
@@ -359,212 +450,17 @@ impl Searcher {
         weight_by_field: Option<&str>,
     ) -> PyResult<SearchResult> {
         py.detach(move || {
-            let mut multicollector = MultiCollector::new();
-
-            let count_handle = if count {
-                Some(multicollector.add_collector(Count))
-            } else {
-                None
-            };
-
-            let (mut multifruit, hits) = {
-                let collector = TopDocs::with_limit(limit).and_offset(offset);
-                if let Some(weight_by_field) = weight_by_field {
-                    let weight_by_field = weight_by_field.to_string();
-
-                    // Get field type from schema
-                    let schema = self.inner.schema();
-                    let field = crate::get_field(schema, &weight_by_field)
-                        .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                    let field_entry = schema.get_field_entry(field);
-                    let field_type = field_entry.field_type().value_type();
-
-                    if !field_entry.is_fast() {
-                        return Err(PyValueError::new_err(format!(
-                            "Field '{}' is not a fast field. The field must be declared with fast=True in the schema.",
-                            weight_by_field
-                        )));
-                    }
-
-                    // Check if field type is supported
-                    if !matches!(field_type, tv::schema::Type::F64 | tv::schema::Type::I64 | tv::schema::Type::U64) {
-                        return Err(PyValueError::new_err(format!(
-                            "Unsupported field type for weighting: {:?}. Only f64, i64, and u64 fastfields are supported.",
-                            field_type
-                        )));
-                    }
-
-                    let collector = collector.tweak_score(
-                        move |segment_reader: &tv::SegmentReader| {
-                            // Create all three readers upfront. Only one will succeed based on
-                            // the actual field type, but we must create all three because:
-                            // 1. Rust closures have a single concrete type - we can't return
-                            //    different closure types from different match arms
-                            // 2. The alternative (Box<dyn Fn>) adds heap allocation per segment
-                            //    and virtual dispatch overhead per document
-                            // 3. This approach enables monomorphization: the inner closure has
-                            //    a concrete type, allowing LLVM to inline get_val() calls
-                            let f64_reader = segment_reader
-                                .fast_fields()
-                                .f64(&weight_by_field)
-                                .ok()
-                                .map(|r| r.first_or_default_col(0.0));
-                            let i64_reader = segment_reader
-                                .fast_fields()
-                                .i64(&weight_by_field)
-                                .ok()
-                                .map(|r| r.first_or_default_col(0));
-                            let u64_reader = segment_reader
-                                .fast_fields()
-                                .u64(&weight_by_field)
-                                .ok()
-                                .map(|r| r.first_or_default_col(0));
-
-                            move |doc: tv::DocId, original_score: tv::Score| {
-                                let value: f64 = match field_type {
-                                    // Runtime type dispatch is required here even though field_type
-                                    // was checked earlier because:
-                                    // 1. field_type is moved into this closure and can't be matched
-                                    //    at compile time to select which reader to use
-                                    // 2. All three readers must exist at this point for the closure
-                                    //    to have a single concrete type
-                                    //
-                                    // Use map_or(0.0, ...) instead of unwrap() because segments
-                                    // created before a schema change may lack this fast field.
-                                    // Default value 0.0 results in neutral scoring:
-                                    // boost = log2(2.0 + 0.0) = 1.0, so score * 1.0 = score
-                                    tv::schema::Type::F64 => f64_reader.as_ref().map_or(0.0, |r| r.get_val(doc)),
-                                    tv::schema::Type::I64 => i64_reader.as_ref().map_or(0.0, |r| r.get_val(doc) as f64),
-                                    tv::schema::Type::U64 => u64_reader.as_ref().map_or(0.0, |r| r.get_val(doc) as f64),
-                                    _ => unreachable!(),
-                                };
-                                let value = value.max(0.0); // Negative values are not allowed
-                                let value_boost_score = ((2f64 + value) as tv::Score).log2();
-                                value_boost_score * original_score
-                            }
-                        },
-                    );
-                    let top_docs_handle =
-                        multicollector.add_collector(collector);
-                    let ret = self.inner.search(query.get(), &multicollector);
-                    match ret {
-                        Ok(mut r) => {
-                            let top_docs = top_docs_handle.extract(&mut r);
-                            let result: Vec<(Fruit, DocAddress)> = top_docs
-                                .iter()
-                                .map(|(f, d)| {
-                                    (Fruit::Score(*f), DocAddress::from(d))
-                                })
-                                .collect();
-                            (r, result)
-                        }
-                        Err(e) => {
-                            return Err(PyValueError::new_err(e.to_string()))
-                        }
-                    }
-                } else if let Some(order_by) = order_by_field {
-                    let schema = self.inner.schema();
-                    let field = crate::get_field(schema, order_by)
-                        .map_err(|e| PyValueError::new_err(e.to_string()))?;
-                    let field_type =
-                        schema.get_field_entry(field).field_type().value_type();
-                    macro_rules! run_order_by_fast {
-                        ($t:ty, $to_fruit:expr) => {{
-                            let top_docs_handle = multicollector.add_collector(
-                                collector.order_by_fast_field::<$t>(order_by, order.into()),
-                            );
-                            let ret =
-                                self.inner.search(query.get(), &multicollector);
-                            match ret {
-                                Ok(mut r) => {
-                                    let top_docs = top_docs_handle.extract(&mut r);
-                                    let result: Vec<(Fruit, DocAddress)> = top_docs
-                                        .into_iter()
-                                        .map(|(f, d)| {
-                                            (
-                                                $to_fruit(f),
-                                                DocAddress::from(&d),
-                                            )
-                                        })
-                                        .collect();
-                                    (r, result)
-                                }
-                                Err(e) => {
-                                    return Err(PyValueError::new_err(e.to_string()))
-                                }
-                            }
-                        }};
-                    }
-                    match field_type {
-                        tv::schema::Type::U64  => run_order_by_fast!(u64, Fruit::OrderU64),
-                        tv::schema::Type::I64  => run_order_by_fast!(i64, Fruit::OrderI64),
-                        tv::schema::Type::F64  => run_order_by_fast!(f64, Fruit::OrderF64),
-                        tv::schema::Type::Bool => run_order_by_fast!(bool, Fruit::OrderBool),
-                        tv::schema::Type::Date => run_order_by_fast!(
-                            tv::DateTime,
-                            |f: Option<tv::DateTime>| {
-                                Fruit::OrderDate(f.map(|dt| dt.into_timestamp_nanos()))
-                            }
-                        ),
-                        tv::schema::Type::Str  => {
-                            let top_docs_handle = multicollector.add_collector(
-                                collector.order_by_string_fast_field(order_by, order.into()),
-                            );
-                            let ret =
-                                self.inner.search(query.get(), &multicollector);
-                            match ret {
-                                Ok(mut r) => {
-                                    let top_docs = top_docs_handle.extract(&mut r);
-                                    let result: Vec<(Fruit, DocAddress)> = top_docs
-                                        .into_iter()
-                                        .map(|(f, d)| {
-                                            (
-                                                Fruit::OrderStr(f),
-                                                DocAddress::from(&d),
-                                            )
-                                        })
-                                        .collect();
-                                    (r, result)
-                                }
-                                Err(e) => {
-                                    return Err(PyValueError::new_err(e.to_string()))
-                                }
-                            }
-                        }
-                        other => {
-                            return Err(PyValueError::new_err(format!(
-                                "Field '{}' has type {:?}; order_by_field only supports \
-                                 Text, Unsigned, Integer, Float, Boolean and Date fast fields.",
-                                order_by, other
-                            )));
-                        }
-                    }
-                } else {
-                    let top_docs_handle = multicollector
-                        .add_collector(collector.order_by_score());
-                    let ret = self.inner.search(query.get(), &multicollector);
-
-                    match ret {
-                        Ok(mut r) => {
-                            let top_docs = top_docs_handle.extract(&mut r);
-                            let result: Vec<(Fruit, DocAddress)> = top_docs
-                                .iter()
-                                .map(|(f, d)| {
-                                    (Fruit::Score(*f), DocAddress::from(d))
-                                })
-                                .collect();
-                            (r, result)
-                        }
-                        Err(e) => {
-                            return Err(PyValueError::new_err(e.to_string()))
-                        }
-                    }
-                }
-            };
-
-            let count = count_handle.map(|h| h.extract(&mut multifruit));
-
-            Ok(SearchResult { hits, count })
+            search_top_docs(
+                &self.inner,
+                &self.inner,
+                query,
+                limit,
+                count,
+                order_by_field,
+                offset,
+                order,
+                weight_by_field,
+            )
         })
     }
 
@@ -963,6 +859,579 @@ impl Searcher {
     }
 }
 
+/// Runs the search of `Searcher.search` on `searcher`, scoring documents
+/// with the BM25 statistics given by `statistics`.
+#[allow(clippy::too_many_arguments)]
+fn search_top_docs(
+    searcher: &tv::Searcher,
+    statistics: &dyn Bm25StatisticsProvider,
+    query: &Query,
+    limit: usize,
+    count: bool,
+    order_by_field: Option<&str>,
+    offset: usize,
+    order: Order,
+    weight_by_field: Option<&str>,
+) -> PyResult<SearchResult> {
+    let mut multicollector = MultiCollector::new();
+
+    let count_handle = if count {
+        Some(multicollector.add_collector(Count))
+    } else {
+        None
+    };
+
+    let (mut multifruit, hits) = {
+        let collector = TopDocs::with_limit(limit).and_offset(offset);
+        if let Some(weight_by_field) = weight_by_field {
+            let weight_by_field = weight_by_field.to_string();
+
+            // Get field type from schema
+            let schema = searcher.schema();
+            let field = crate::get_field(schema, &weight_by_field)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            let field_entry = schema.get_field_entry(field);
+            let field_type = field_entry.field_type().value_type();
+
+            if !field_entry.is_fast() {
+                return Err(PyValueError::new_err(format!(
+                    "Field '{}' is not a fast field. The field must be declared with fast=True in the schema.",
+                    weight_by_field
+                )));
+            }
+
+            // Check if field type is supported
+            if !matches!(
+                field_type,
+                tv::schema::Type::F64
+                    | tv::schema::Type::I64
+                    | tv::schema::Type::U64
+            ) {
+                return Err(PyValueError::new_err(format!(
+                    "Unsupported field type for weighting: {:?}. Only f64, i64, and u64 fastfields are supported.",
+                    field_type
+                )));
+            }
+
+            let collector = collector.tweak_score(
+                move |segment_reader: &tv::SegmentReader| {
+                    // Create all three readers upfront. Only one will succeed based on
+                    // the actual field type, but we must create all three because:
+                    // 1. Rust closures have a single concrete type - we can't return
+                    //    different closure types from different match arms
+                    // 2. The alternative (Box<dyn Fn>) adds heap allocation per segment
+                    //    and virtual dispatch overhead per document
+                    // 3. This approach enables monomorphization: the inner closure has
+                    //    a concrete type, allowing LLVM to inline get_val() calls
+                    let f64_reader = segment_reader
+                        .fast_fields()
+                        .f64(&weight_by_field)
+                        .ok()
+                        .map(|r| r.first_or_default_col(0.0));
+                    let i64_reader = segment_reader
+                        .fast_fields()
+                        .i64(&weight_by_field)
+                        .ok()
+                        .map(|r| r.first_or_default_col(0));
+                    let u64_reader = segment_reader
+                        .fast_fields()
+                        .u64(&weight_by_field)
+                        .ok()
+                        .map(|r| r.first_or_default_col(0));
+
+                    move |doc: tv::DocId, original_score: tv::Score| {
+                        let value: f64 = match field_type {
+                            // Runtime type dispatch is required here even though field_type
+                            // was checked earlier because:
+                            // 1. field_type is moved into this closure and can't be matched
+                            //    at compile time to select which reader to use
+                            // 2. All three readers must exist at this point for the closure
+                            //    to have a single concrete type
+                            //
+                            // Use map_or(0.0, ...) instead of unwrap() because segments
+                            // created before a schema change may lack this fast field.
+                            // Default value 0.0 results in neutral scoring:
+                            // boost = log2(2.0 + 0.0) = 1.0, so score * 1.0 = score
+                            tv::schema::Type::F64 => f64_reader
+                                .as_ref()
+                                .map_or(0.0, |r| r.get_val(doc)),
+                            tv::schema::Type::I64 => i64_reader
+                                .as_ref()
+                                .map_or(0.0, |r| r.get_val(doc) as f64),
+                            tv::schema::Type::U64 => u64_reader
+                                .as_ref()
+                                .map_or(0.0, |r| r.get_val(doc) as f64),
+                            _ => unreachable!(),
+                        };
+                        let value = value.max(0.0); // Negative values are not allowed
+                        let value_boost_score =
+                            ((2f64 + value) as tv::Score).log2();
+                        value_boost_score * original_score
+                    }
+                },
+            );
+            let top_docs_handle = multicollector.add_collector(collector);
+            let ret = searcher.search_with_statistics_provider(
+                query.get(),
+                &multicollector,
+                statistics,
+            );
+            match ret {
+                Ok(mut r) => {
+                    let top_docs = top_docs_handle.extract(&mut r);
+                    let result: Vec<(Fruit, DocAddress)> = top_docs
+                        .iter()
+                        .map(|(f, d)| (Fruit::Score(*f), DocAddress::from(d)))
+                        .collect();
+                    (r, result)
+                }
+                Err(e) => return Err(PyValueError::new_err(e.to_string())),
+            }
+        } else if let Some(order_by) = order_by_field {
+            let schema = searcher.schema();
+            let field = crate::get_field(schema, order_by)
+                .map_err(|e| PyValueError::new_err(e.to_string()))?;
+            let field_type =
+                schema.get_field_entry(field).field_type().value_type();
+            macro_rules! run_order_by_fast {
+                ($t:ty, $to_fruit:expr) => {{
+                    let top_docs_handle = multicollector.add_collector(
+                        collector
+                            .order_by_fast_field::<$t>(order_by, order.into()),
+                    );
+                    let ret = searcher.search_with_statistics_provider(
+                        query.get(),
+                        &multicollector,
+                        statistics,
+                    );
+                    match ret {
+                        Ok(mut r) => {
+                            let top_docs = top_docs_handle.extract(&mut r);
+                            let result: Vec<(Fruit, DocAddress)> = top_docs
+                                .into_iter()
+                                .map(|(f, d)| {
+                                    ($to_fruit(f), DocAddress::from(&d))
+                                })
+                                .collect();
+                            (r, result)
+                        }
+                        Err(e) => {
+                            return Err(PyValueError::new_err(e.to_string()))
+                        }
+                    }
+                }};
+            }
+            match field_type {
+                tv::schema::Type::U64 => {
+                    run_order_by_fast!(u64, Fruit::OrderU64)
+                }
+                tv::schema::Type::I64 => {
+                    run_order_by_fast!(i64, Fruit::OrderI64)
+                }
+                tv::schema::Type::F64 => {
+                    run_order_by_fast!(f64, Fruit::OrderF64)
+                }
+                tv::schema::Type::Bool => {
+                    run_order_by_fast!(bool, Fruit::OrderBool)
+                }
+                tv::schema::Type::Date => {
+                    run_order_by_fast!(tv::DateTime, |f: Option<
+                        tv::DateTime,
+                    >| {
+                        Fruit::OrderDate(f.map(|dt| dt.into_timestamp_nanos()))
+                    })
+                }
+                tv::schema::Type::Str => {
+                    let top_docs_handle = multicollector.add_collector(
+                        collector
+                            .order_by_string_fast_field(order_by, order.into()),
+                    );
+                    let ret = searcher.search_with_statistics_provider(
+                        query.get(),
+                        &multicollector,
+                        statistics,
+                    );
+                    match ret {
+                        Ok(mut r) => {
+                            let top_docs = top_docs_handle.extract(&mut r);
+                            let result: Vec<(Fruit, DocAddress)> = top_docs
+                                .into_iter()
+                                .map(|(f, d)| {
+                                    (Fruit::OrderStr(f), DocAddress::from(&d))
+                                })
+                                .collect();
+                            (r, result)
+                        }
+                        Err(e) => {
+                            return Err(PyValueError::new_err(e.to_string()))
+                        }
+                    }
+                }
+                other => {
+                    return Err(PyValueError::new_err(format!(
+                        "Field '{}' has type {:?}; order_by_field only supports \
+                         Text, Unsigned, Integer, Float, Boolean and Date fast fields.",
+                        order_by, other
+                    )));
+                }
+            }
+        } else {
+            let top_docs_handle =
+                multicollector.add_collector(collector.order_by_score());
+            let ret = searcher.search_with_statistics_provider(
+                query.get(),
+                &multicollector,
+                statistics,
+            );
+
+            match ret {
+                Ok(mut r) => {
+                    let top_docs = top_docs_handle.extract(&mut r);
+                    let result: Vec<(Fruit, DocAddress)> = top_docs
+                        .iter()
+                        .map(|(f, d)| (Fruit::Score(*f), DocAddress::from(d)))
+                        .collect();
+                    (r, result)
+                }
+                Err(e) => return Err(PyValueError::new_err(e.to_string())),
+            }
+        }
+    };
+
+    let count = count_handle.map(|h| h.extract(&mut multifruit));
+
+    Ok(SearchResult { hits, count })
+}
+
+/// BM25 statistics summed over several searchers, so that the scores of
+/// their documents are comparable.
+struct GlobalStatistics<'a> {
+    searchers: &'a [tv::Searcher],
+}
+
+impl Bm25StatisticsProvider for GlobalStatistics<'_> {
+    fn total_num_tokens(&self, field: tv::schema::Field) -> tv::Result<u64> {
+        self.searchers
+            .iter()
+            .map(|searcher| searcher.total_num_tokens(field))
+            .sum()
+    }
+
+    fn total_num_docs(&self) -> tv::Result<u64> {
+        self.searchers
+            .iter()
+            .map(|searcher| searcher.total_num_docs())
+            .sum()
+    }
+
+    fn doc_freq(&self, term: &tv::Term) -> tv::Result<u64> {
+        self.searchers
+            .iter()
+            .map(|searcher| Bm25StatisticsProvider::doc_freq(searcher, term))
+            .sum()
+    }
+}
+
+/// Orders two hits as `TopDocs` does: scores are sorted in descending order,
+/// and documents without a value for the sort field come last.
+fn compare_fruits(left: &Fruit, right: &Fruit, order: Order) -> Ordering {
+    fn compare<T: PartialOrd>(
+        left: &Option<T>,
+        right: &Option<T>,
+        order: Order,
+    ) -> Ordering {
+        match (left, right) {
+            (Some(left), Some(right)) => {
+                let ordering =
+                    left.partial_cmp(right).unwrap_or(Ordering::Equal);
+                match order {
+                    Order::Asc => ordering,
+                    Order::Desc => ordering.reverse(),
+                }
+            }
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+    match (left, right) {
+        (Fruit::Score(left), Fruit::Score(right)) => right.total_cmp(left),
+        (Fruit::OrderU64(left), Fruit::OrderU64(right)) => {
+            compare(left, right, order)
+        }
+        (Fruit::OrderI64(left), Fruit::OrderI64(right))
+        | (Fruit::OrderDate(left), Fruit::OrderDate(right)) => {
+            compare(left, right, order)
+        }
+        (Fruit::OrderF64(left), Fruit::OrderF64(right)) => {
+            compare(left, right, order)
+        }
+        (Fruit::OrderBool(left), Fruit::OrderBool(right)) => {
+            compare(left, right, order)
+        }
+        (Fruit::OrderStr(left), Fruit::OrderStr(right)) => {
+            compare(left, right, order)
+        }
+        _ => Ordering::Equal,
+    }
+}
+
+/// Searches several indexes, or shards, sharing the same schema as if they
+/// were a single one.
+///
+/// The documents are scored with the BM25 statistics of all the shards, so
+/// that their scores can be compared, and each hit tells the position of the
+/// shard it comes from in the list of searchers.
+///
+/// Args:
+///     searchers (list[Searcher]): The searchers of the shards.
+#[pyclass(frozen, module = "tantivy.tantivy")]
+pub(crate) struct MultiSearcher {
+    searchers: Vec<tv::Searcher>,
+}
+
+#[pymethods]
+impl MultiSearcher {
+    #[new]
+    fn new(searchers: Vec<PyRef<Searcher>>) -> PyResult<Self> {
+        let searchers: Vec<tv::Searcher> = searchers
+            .iter()
+            .map(|searcher| searcher.inner.clone())
+            .collect();
+        let Some(first) = searchers.first() else {
+            return Err(PyValueError::new_err(
+                "At least one searcher must be given.",
+            ));
+        };
+        if searchers
+            .iter()
+            .any(|searcher| searcher.schema() != first.schema())
+        {
+            return Err(PyValueError::new_err(
+                "All the searchers must have the same schema.",
+            ));
+        }
+        Ok(MultiSearcher { searchers })
+    }
+
+    /// Search all the shards in parallel with the given query and merge
+    /// their results.
+    ///
+    /// Takes the same arguments as `Searcher.search`.
+    ///
+    /// Returns a `MultiSearchResult` object whose `hits` is a list of
+    /// `(order_key, shard, DocAddress)` tuples, where `shard` is the position
+    /// of the searcher in the list given to the `MultiSearcher`.
+    ///
+    /// Raises a ValueError if there was an error with the search.
+    #[pyo3(signature = (query, limit = 10, count = true, order_by_field = None, offset = 0, order = Order::Desc,
+            weight_by_field = None))]
+    #[allow(clippy::too_many_arguments)]
+    fn search(
+        &self,
+        py: Python,
+        query: &Query,
+        limit: usize,
+        count: bool,
+        order_by_field: Option<&str>,
+        offset: usize,
+        order: Order,
+        weight_by_field: Option<&str>,
+    ) -> PyResult<MultiSearchResult> {
+        py.detach(move || {
+            let statistics = GlobalStatistics {
+                searchers: &self.searchers,
+            };
+            // Every shard may hold all the hits of the requested page.
+            let shard_limit = limit.saturating_add(offset);
+            let results = std::thread::scope(|scope| {
+                let handles: Vec<_> = self
+                    .searchers
+                    .iter()
+                    .map(|searcher| {
+                        let statistics = &statistics;
+                        scope.spawn(move || {
+                            search_top_docs(
+                                searcher,
+                                statistics,
+                                query,
+                                shard_limit,
+                                count,
+                                order_by_field,
+                                0,
+                                order,
+                                weight_by_field,
+                            )
+                        })
+                    })
+                    .collect();
+                handles
+                    .into_iter()
+                    .map(|handle| {
+                        handle.join().unwrap_or_else(|panic| {
+                            let message = panic
+                                .downcast_ref::<&str>()
+                                .map(|message| message.to_string())
+                                .or_else(|| {
+                                    panic.downcast_ref::<String>().cloned()
+                                })
+                                .unwrap_or_default();
+                            Err(PyValueError::new_err(format!(
+                                "Searching a shard failed: {message}"
+                            )))
+                        })
+                    })
+                    .collect::<PyResult<Vec<_>>>()
+            })?;
+
+            let count = count.then(|| {
+                results.iter().filter_map(|result| result.count).sum()
+            });
+            let mut hits: Vec<(Fruit, usize, DocAddress)> = results
+                .into_iter()
+                .enumerate()
+                .flat_map(|(shard, result)| {
+                    result
+                        .hits
+                        .into_iter()
+                        .map(move |(fruit, address)| (fruit, shard, address))
+                })
+                .collect();
+            hits.sort_by(|left, right| {
+                compare_fruits(&left.0, &right.0, order)
+                    .then_with(|| (left.1, &left.2).cmp(&(right.1, &right.2)))
+            });
+            let hits = hits.into_iter().skip(offset).take(limit).collect();
+            Ok(MultiSearchResult { hits, count })
+        })
+    }
+
+    /// Fetches a document from the store of a shard.
+    ///
+    /// Args:
+    ///     shard (int): The position of the shard's searcher, as given in
+    ///         the search hits.
+    ///     doc_address (DocAddress): The DocAddress of the document in the
+    ///         shard.
+    ///
+    /// Returns the Document, raises ValueError if the document can't be found.
+    fn doc(
+        &self,
+        py: Python,
+        shard: usize,
+        doc_address: &DocAddress,
+    ) -> PyResult<Document> {
+        let searcher = self.searchers.get(shard).ok_or_else(|| {
+            PyValueError::new_err(format!(
+                "Invalid shard {shard}, there are {} shards.",
+                self.searchers.len()
+            ))
+        })?;
+        let addr: tv::DocAddress = doc_address.into();
+        py.detach(move || {
+            let doc: TantivyDocument = searcher.doc(addr).map_err(to_pyerr)?;
+            let named_doc = doc.to_named_doc(searcher.schema());
+            Ok(crate::document::Document {
+                field_values: named_doc.0,
+            })
+        })
+    }
+
+    /// Returns the overall number of documents in all the shards.
+    #[getter]
+    fn num_docs(&self) -> u64 {
+        self.searchers.iter().map(tv::Searcher::num_docs).sum()
+    }
+
+    /// Returns the number of shards.
+    #[getter]
+    fn num_shards(&self) -> usize {
+        self.searchers.len()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "MultiSearcher(num_docs={}, num_shards={})",
+            self.num_docs(),
+            self.num_shards()
+        )
+    }
+}
+
+/// A search hit of a `MultiSearcher` as exposed to Python: the (score or
+/// sort key) object, the shard and the address of the document in it.
+type PyMultiHit = (Py<PyAny>, usize, DocAddress);
+
+#[pyclass(frozen, module = "tantivy.tantivy")]
+#[derive(Clone, Default, Deserialize, PartialEq, Serialize)]
+/// Object holding the results of a search on several shards.
+pub(crate) struct MultiSearchResult {
+    hits: Vec<(Fruit, usize, DocAddress)>,
+    #[pyo3(get)]
+    /// How many documents matched the query in all the shards. Only available
+    /// if `count` was set to true during the search.
+    count: Option<usize>,
+}
+
+#[pymethods]
+impl MultiSearchResult {
+    #[new]
+    fn new(
+        py: Python,
+        hits: Vec<PyMultiHit>,
+        count: Option<usize>,
+    ) -> PyResult<Self> {
+        let hits = hits
+            .iter()
+            .map(|(f, shard, d)| Ok((f.extract(py)?, *shard, d.clone())))
+            .collect::<PyResult<Vec<_>>>()?;
+        Ok(Self { hits, count })
+    }
+
+    fn __repr__(&self) -> PyResult<String> {
+        if let Some(count) = self.count {
+            Ok(format!(
+                "MultiSearchResult(hits: {:?}, count: {})",
+                self.hits, count
+            ))
+        } else {
+            Ok(format!("MultiSearchResult(hits: {:?})", self.hits))
+        }
+    }
+
+    fn __richcmp__(
+        &self,
+        other: &Self,
+        op: CompareOp,
+        py: Python<'_>,
+    ) -> PyResult<Py<PyAny>> {
+        match op {
+            CompareOp::Eq => (self == other).into_py_any(py),
+            CompareOp::Ne => (self != other).into_py_any(py),
+            _ => Ok(py.NotImplemented()),
+        }
+    }
+
+    fn __getnewargs__(
+        &self,
+        py: Python,
+    ) -> PyResult<(Vec<PyMultiHit>, Option<usize>)> {
+        Ok((self.hits(py)?, self.count))
+    }
+
+    #[getter]
+    /// The list of tuples that contains the scores, shards and DocAddress of
+    /// the search results.
+    fn hits(&self, py: Python) -> PyResult<Vec<PyMultiHit>> {
+        self.hits
+            .iter()
+            .map(|(result, shard, address)| -> PyResult<_> {
+                Ok((result.clone().into_py_any(py)?, *shard, address.clone()))
+            })
+            .collect()
+    }
+}
+
 /// DocAddress contains all the necessary information to identify a document
 /// given a Searcher object.
 ///
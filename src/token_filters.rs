@@ -0,0 +1,308 @@
+//! Token filters that tantivy does not ship but that are exposed through
+//! `Filter` so they can be used from a `TextAnalyzerBuilder`.
+
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::Arc,
+};
+
+use tantivy::tokenizer::{Token, TokenFilter, TokenStream, Tokenizer};
+
+/// Replaces each token by its prefixes of `min_gram..=max_gram` characters.
+///
+/// All the grams of a token share its position and offsets, so phrase
+/// queries and highlighting keep working on the original text.
+#[derive(Clone)]
+pub(crate) struct EdgeNgramFilter {
+    min_gram: usize,
+    max_gram: usize,
+    preserve_original: bool,
+}
+
+impl EdgeNgramFilter {
+    pub(crate) fn new(
+        min_gram: usize,
+        max_gram: usize,
+        preserve_original: bool,
+    ) -> Result<Self, String> {
+        if min_gram == 0 {
+            return Err("min_gram must be greater than zero".to_string());
+        }
+        if min_gram > max_gram {
+            return Err(format!(
+                "min_gram ({min_gram}) must not be greater than max_gram ({max_gram})"
+            ));
+        }
+        Ok(EdgeNgramFilter {
+            min_gram,
+            max_gram,
+            preserve_original,
+        })
+    }
+}
+
+impl TokenFilter for EdgeNgramFilter {
+    type Tokenizer<T: Tokenizer> = EdgeNgramFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> Self::Tokenizer<T> {
+        EdgeNgramFilterWrapper {
+            filter: self,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct EdgeNgramFilterWrapper<T> {
+    filter: EdgeNgramFilter,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for EdgeNgramFilterWrapper<T> {
+    type TokenStream<'a> = EdgeNgramTokenStream<T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        EdgeNgramTokenStream {
+            filter: self.filter.clone(),
+            tail: self.inner.token_stream(text),
+            pending: VecDeque::new(),
+            token: Token::default(),
+        }
+    }
+}
+
+pub(crate) struct EdgeNgramTokenStream<T> {
+    filter: EdgeNgramFilter,
+    tail: T,
+    pending: VecDeque<Token>,
+    token: Token,
+}
+
+impl<T: TokenStream> EdgeNgramTokenStream<T> {
+    fn fill_pending(&mut self) {
+        let source = self.tail.token();
+        let num_chars = source.text.chars().count();
+        let mut byte_ends = source
+            .text
+            .char_indices()
+            .map(|(idx, c)| idx + c.len_utf8());
+        let mut previous_len = 0;
+        for gram_len in self.filter.min_gram..=self.filter.max_gram {
+            if gram_len > num_chars {
+                break;
+            }
+            let end = byte_ends
+                .nth(gram_len - previous_len - 1)
+                .expect("gram_len is bounded by the number of chars");
+            previous_len = gram_len;
+            self.pending.push_back(Token {
+                text: source.text[..end].to_string(),
+                ..source.clone()
+            });
+        }
+        let already_emitted =
+            (self.filter.min_gram..=self.filter.max_gram).contains(&num_chars);
+        if self.filter.preserve_original && !already_emitted {
+            self.pending.push_back(source.clone());
+        }
+    }
+}
+
+impl<T: TokenStream> TokenStream for EdgeNgramTokenStream<T> {
+    fn advance(&mut self) -> bool {
+        loop {
+            if let Some(token) = self.pending.pop_front() {
+                self.token = token;
+                return true;
+            }
+            if !self.tail.advance() {
+                return false;
+            }
+            self.fill_pending();
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
+
+/// Injects synonyms next to the tokens they are defined for.
+///
+/// Keys of the synonym map are sequences of one or more token texts. The
+/// longest key matching at a given position wins. The original tokens are
+/// always kept; each synonym is emitted at the position of the first matched
+/// token, a multi-word synonym taking consecutive positions from there, up to
+/// the position of the last matched token. The extra words of a synonym
+/// longer than its key share that last position, so that they never land on
+/// the position of a token following the match. A single-word synonym
+/// replacing several tokens gets a `position_length` equal to the number of
+/// tokens it replaces.
+#[derive(Clone)]
+pub(crate) struct SynonymFilter {
+    synonyms: Arc<HashMap<Vec<String>, Vec<Vec<String>>>>,
+    max_key_len: usize,
+}
+
+impl SynonymFilter {
+    /// Builds the filter from `(phrase, synonyms)` pairs, where phrases are
+    /// whitespace separated token texts.
+    pub(crate) fn new<'a, I>(entries: I) -> Result<Self, String>
+    where
+        I: IntoIterator<Item = (&'a String, &'a Vec<String>)>,
+    {
+        fn split(phrase: &str) -> Vec<String> {
+            phrase.split_whitespace().map(str::to_string).collect()
+        }
+
+        let mut synonyms: HashMap<Vec<String>, Vec<Vec<String>>> =
+            HashMap::new();
+        for (phrase, replacements) in entries {
+            let key = split(phrase);
+            if key.is_empty() {
+                return Err("Synonym keys must not be empty".to_string());
+            }
+            let values = synonyms.entry(key.clone()).or_default();
+            for replacement in replacements {
+                let value = split(replacement);
+                if value.is_empty() {
+                    return Err(format!("Empty synonym given for `{phrase}`"));
+                }
+                if value != key && !values.contains(&value) {
+                    values.push(value);
+                }
+            }
+        }
+        let max_key_len = synonyms.keys().map(Vec::len).max().unwrap_or(0);
+        Ok(SynonymFilter {
+            synonyms: Arc::new(synonyms),
+            max_key_len,
+        })
+    }
+}
+
+impl TokenFilter for SynonymFilter {
+    type Tokenizer<T: Tokenizer> = SynonymFilterWrapper<T>;
+
+    fn transform<T: Tokenizer>(self, tokenizer: T) -> Self::Tokenizer<T> {
+        SynonymFilterWrapper {
+            filter: self,
+            inner: tokenizer,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct SynonymFilterWrapper<T> {
+    filter: SynonymFilter,
+    inner: T,
+}
+
+impl<T: Tokenizer> Tokenizer for SynonymFilterWrapper<T> {
+    type TokenStream<'a> = SynonymTokenStream<'a, T::TokenStream<'a>>;
+
+    fn token_stream<'a>(&'a mut self, text: &'a str) -> Self::TokenStream<'a> {
+        SynonymTokenStream {
+            filter: &self.filter,
+            tail: self.inner.token_stream(text),
+            lookahead: VecDeque::new(),
+            pending: VecDeque::new(),
+            token: Token::default(),
+        }
+    }
+}
+
+pub(crate) struct SynonymTokenStream<'a, T> {
+    filter: &'a SynonymFilter,
+    tail: T,
+    /// Tokens read from `tail` but not emitted yet, used to match
+    /// multi-word keys.
+    lookahead: VecDeque<Token>,
+    /// Tokens ready to be emitted.
+    pending: VecDeque<Token>,
+    token: Token,
+}
+
+impl<T: TokenStream> SynonymTokenStream<'_, T> {
+    /// Returns the synonyms of the longest key starting at the front of the
+    /// lookahead, along with the number of tokens it covers.
+    fn longest_match(&self) -> Option<(usize, &Vec<Vec<String>>)> {
+        let max_len = self.filter.max_key_len.min(self.lookahead.len());
+        (1..=max_len).rev().find_map(|len| {
+            let key: Vec<String> = self
+                .lookahead
+                .iter()
+                .take(len)
+                .map(|token| token.text.clone())
+                .collect();
+            self.filter
+                .synonyms
+                .get(&key)
+                .filter(|values| !values.is_empty())
+                .map(|values| (len, values))
+        })
+    }
+
+    fn fill_pending(&mut self) {
+        let matched = self.longest_match().map(|(len, values)| {
+            let last = &self.lookahead[len - 1];
+            (len, last.offset_to, values.clone())
+        });
+        let first = self
+            .lookahead
+            .pop_front()
+            .expect("fill_pending requires a lookahead token");
+        if let Some((len, offset_to, values)) = matched {
+            self.pending.push_back(first.clone());
+            for value in values {
+                let position_length = if value.len() == 1 { len } else { 1 };
+                for (idx, text) in value.into_iter().enumerate() {
+                    self.pending.push_back(Token {
+                        offset_from: first.offset_from,
+                        offset_to,
+                        position: first.position + idx.min(len - 1),
+                        text,
+                        position_length,
+                    });
+                }
+            }
+        } else {
+            self.pending.push_back(first);
+        }
+    }
+}
+
+impl<T: TokenStream> TokenStream for SynonymTokenStream<'_, T> {
+    fn advance(&mut self) -> bool {
+        if self.pending.is_empty() {
+            while self.lookahead.len() < self.filter.max_key_len.max(1)
+                && self.tail.advance()
+            {
+                self.lookahead.push_back(self.tail.token().clone());
+            }
+            if self.lookahead.is_empty() {
+                return false;
+            }
+            self.fill_pending();
+        }
+        match self.pending.pop_front() {
+            Some(token) => {
+                self.token = token;
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn token(&self) -> &Token {
+        &self.token
+    }
+
+    fn token_mut(&mut self) -> &mut Token {
+        &mut self.token
+    }
+}
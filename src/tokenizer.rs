@@ -1,6 +1,15 @@
-use pyo3::{exceptions::PyValueError, prelude::*};
+use pyo3::{
+    basic::CompareOp, exceptions::PyValueError, prelude::*, IntoPyObjectExt,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use tantivy::tokenizer as tvt;
 
+use crate::{
+    to_pyerr,
+    token_filters::{EdgeNgramFilter, SynonymFilter},
+};
+
 /// All Tantivy's built-in tokenizers in one place.
 /// Each static method, e.g. Tokenizer.simple(),
 /// creates a wrapper around a Tantivy tokenizer.
@@ -29,20 +38,28 @@ use tantivy::tokenizer as tvt;
 // does not have the #[pyo3(constructor = ...)],
 // attribute yet, making it more sensible to
 // define constructor signatures using a separate method.
+//
+// The serde representation is the one used by `TextAnalyzer.to_json()`:
+// the `type` key holds the name of the static method creating the variant.
 #[pyclass(module = "tantivy.tokenizer")]
-#[derive(Debug)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type")]
 pub enum Tokenizer {
+    #[serde(rename = "raw")]
     _Raw {},
+    #[serde(rename = "simple")]
     _Simple {},
+    #[serde(rename = "whitespace")]
     _Whitespace {},
-    _Regex {
-        pattern: String,
-    },
+    #[serde(rename = "regex")]
+    _Regex { pattern: String },
+    #[serde(rename = "ngram")]
     _Ngram {
         min_gram: usize,
         max_gram: usize,
         prefix_only: bool,
     },
+    #[serde(rename = "facet")]
     _Facet {},
 }
 
@@ -129,17 +146,38 @@ impl Tokenizer {
 // does not have the #[pyo3(constructor = ...)],
 // attribute yet, making it more sensible to
 // define constructor signatures using a separate method.
+//
+// See `Tokenizer` for the serde representation.
 #[pyclass(module = "tantivy.tokenizer")]
-#[derive(Debug)]
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(tag = "type")]
 pub enum Filter {
+    #[serde(rename = "alphanum_only")]
     _AlphaNumOnly {},
+    #[serde(rename = "ascii_fold")]
     _AsciiFolding {},
+    #[serde(rename = "lowercase")]
     _LowerCaser {},
+    #[serde(rename = "remove_long")]
     _RemoveLong { length_limit: usize },
+    #[serde(rename = "stemmer")]
     _Stemmer { language: String },
+    #[serde(rename = "stopword")]
     _StopWord { language: String },
+    #[serde(rename = "custom_stopword")]
     _CustomStopWord { stopwords: Vec<String> },
+    #[serde(rename = "split_compound")]
     _SplitCompound { constituent_words: Vec<String> },
+    #[serde(rename = "edge_ngram")]
+    _EdgeNgram {
+        min_gram: usize,
+        max_gram: usize,
+        preserve_original: bool,
+    },
+    #[serde(rename = "synonym")]
+    _Synonym {
+        synonyms: BTreeMap<String, Vec<String>>,
+    },
 }
 
 #[pymethods]
@@ -224,6 +262,90 @@ impl Filter {
         Ok(Filter::_SplitCompound { constituent_words })
     }
 
+    /// EdgeNgramFilter
+    ///
+    /// Replaces each token by its prefixes, e.g. for autocomplete. All the
+    /// prefixes of a token keep the position and offsets of the token.
+    ///
+    /// Args:
+    /// - min_gram (int): Minimum character length of each prefix.
+    /// - max_gram (int): Maximum character length of each prefix.
+    /// - preserve_original (bool, optional): If true, tokens that are shorter
+    ///   than `min_gram` or longer than `max_gram` are kept as well.
+    ///
+    /// Example:
+    ///
+    /// ```python
+    /// edge = Filter.edge_ngram(min_gram=1, max_gram=3)
+    /// # Will turn 'tantivy' into ['t', 'ta', 'tan']
+    /// ```
+    #[pyo3(signature=(min_gram=1, max_gram=2, preserve_original=false))]
+    #[staticmethod]
+    fn edge_ngram(
+        min_gram: usize,
+        max_gram: usize,
+        preserve_original: bool,
+    ) -> PyResult<Filter> {
+        EdgeNgramFilter::new(min_gram, max_gram, preserve_original)
+            .map_err(PyErr::new::<PyValueError, _>)?;
+        Ok(Filter::_EdgeNgram {
+            min_gram,
+            max_gram,
+            preserve_original,
+        })
+    }
+
+    /// SynonymFilter
+    ///
+    /// Emits synonyms alongside the tokens they are defined for. The
+    /// original tokens are kept, and each synonym is emitted at the position
+    /// of the first token it replaces, so phrase queries keep working.
+    ///
+    /// Keys and synonyms may span several words (separated by whitespace),
+    /// in which case they are matched against consecutive tokens. Matching
+    /// is done on the token text as produced by the previous filters, so
+    /// this filter usually comes after `Filter.lowercase()`. The words of a
+    /// synonym never take positions past the tokens it replaces: the words
+    /// of a synonym longer than its key share the position of the last
+    /// replaced token, so they match on their own but not as a phrase.
+    ///
+    /// Args:
+    /// - synonyms (dict(str, list(str)) | list(list(str))): either a mapping
+    ///   from a phrase to its synonyms, or a list of groups of equivalent
+    ///   phrases.
+    ///
+    /// Example:
+    ///
+    /// ```python
+    /// synonyms = Filter.synonym({"nyc": ["new york"], "tv": ["television"]})
+    /// equivalent = Filter.synonym([["car", "automobile", "motor car"]])
+    /// ```
+    #[staticmethod]
+    fn synonym(synonyms: &Bound<PyAny>) -> PyResult<Filter> {
+        let synonyms = if let Ok(mapping) =
+            synonyms.extract::<BTreeMap<String, Vec<String>>>()
+        {
+            mapping
+        } else {
+            let groups = synonyms.extract::<Vec<Vec<String>>>().map_err(|_| {
+                PyValueError::new_err(
+                    "synonyms must be a dict of str to list of str, or a list of lists of str",
+                )
+            })?;
+            let mut mapping: BTreeMap<String, Vec<String>> = BTreeMap::new();
+            for group in groups {
+                for phrase in &group {
+                    mapping
+                        .entry(phrase.clone())
+                        .or_default()
+                        .extend(group.iter().filter(|p| *p != phrase).cloned());
+                }
+            }
+            mapping
+        };
+        Ok(Filter::_Synonym { synonyms })
+    }
+
     fn __repr__(&self) -> String {
         format!("tantivy.Filter(kind={:?})", &self)
     }
@@ -253,6 +375,90 @@ fn parse_language(lang: &str) -> Result<tvt::Language, String> {
     }
 }
 
+/// A single token produced by a `TextAnalyzer`.
+///
+/// `offset_from` and `offset_to` are byte offsets into the analyzed text,
+/// the same convention used by `Snippet.highlighted()`.
+#[pyclass(frozen, module = "tantivy.tantivy")]
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Token {
+    /// The token text, after all filters have been applied.
+    #[pyo3(get)]
+    text: String,
+    /// Byte offset of the first character of the token in the source text.
+    #[pyo3(get)]
+    offset_from: usize,
+    /// Byte offset right after the last character of the token.
+    #[pyo3(get)]
+    offset_to: usize,
+    /// Position of the token, expressed in number of tokens.
+    #[pyo3(get)]
+    position: usize,
+    /// Number of positions the token spans, usually 1.
+    #[pyo3(get)]
+    position_length: usize,
+}
+
+impl From<&tvt::Token> for Token {
+    fn from(token: &tvt::Token) -> Self {
+        Token {
+            text: token.text.clone(),
+            offset_from: token.offset_from,
+            offset_to: token.offset_to,
+            position: token.position,
+            position_length: token.position_length,
+        }
+    }
+}
+
+#[pymethods]
+impl Token {
+    fn __repr__(&self) -> String {
+        format!(
+            "Token(text={:?}, offset_from={}, offset_to={}, position={}, position_length={})",
+            self.text,
+            self.offset_from,
+            self.offset_to,
+            self.position,
+            self.position_length
+        )
+    }
+
+    fn __richcmp__(
+        &self,
+        other: &Self,
+        op: CompareOp,
+        py: Python<'_>,
+    ) -> PyResult<Py<PyAny>> {
+        match op {
+            CompareOp::Eq => (self == other).into_py_any(py),
+            CompareOp::Ne => (self != other).into_py_any(py),
+            _ => Ok(py.NotImplemented()),
+        }
+    }
+}
+
+/// Serializable description of a `TextAnalyzer`: a tokenizer followed by a
+/// chain of filters. This is what `TextAnalyzer.to_json()` produces and what
+/// `Index` persists next to the index files.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub(crate) struct AnalyzerDefinition {
+    tokenizer: Tokenizer,
+    #[serde(default)]
+    filters: Vec<Filter>,
+}
+
+impl AnalyzerDefinition {
+    /// Builds the tantivy analyzer described by this definition.
+    pub(crate) fn build(&self) -> PyResult<tvt::TextAnalyzer> {
+        let mut builder = tokenizer_builder(&self.tokenizer)?;
+        for filter in &self.filters {
+            builder = add_filter(builder, filter)?;
+        }
+        Ok(builder.build())
+    }
+}
+
 /// Tantivy's TextAnalyzer
 ///
 /// Do not instantiate this class directly.
@@ -261,6 +467,9 @@ fn parse_language(lang: &str) -> Result<tvt::Language, String> {
 #[pyclass(module = "tantivy.tantivy")]
 pub(crate) struct TextAnalyzer {
     pub(crate) analyzer: tvt::TextAnalyzer,
+    /// `None` when the analyzer was not built from Python, e.g. the one
+    /// returned by `Index.tokenizer_for_field()`.
+    pub(crate) definition: Option<AnalyzerDefinition>,
 }
 
 #[pymethods]
@@ -279,6 +488,70 @@ impl TextAnalyzer {
         }
         tokens
     }
+
+    /// Tokenize a string, keeping the full token metadata.
+    ///
+    /// Unlike `analyze()`, which only returns the token texts, this returns
+    /// the offsets, position and position length of each token. This is
+    /// useful to debug an analyzer or to highlight matches client-side.
+    ///
+    /// Args:
+    /// - text (string): text to tokenize.
+    /// Returns:
+    /// - list(Token): the tokens, in the order they were emitted.
+    fn analyze_tokens(&mut self, text: &str) -> Vec<Token> {
+        let mut token_stream = self.analyzer.token_stream(text);
+        let mut tokens = Vec::new();
+
+        while token_stream.advance() {
+            tokens.push(Token::from(token_stream.token()));
+        }
+        tokens
+    }
+
+    /// Serialize the analyzer definition (tokenizer and filters) to JSON.
+    ///
+    /// The result can be turned back into an analyzer with
+    /// `TextAnalyzer.from_json()`.
+    ///
+    /// Raises ValueError if the analyzer was not built with a
+    /// `TextAnalyzerBuilder`.
+    fn to_json(&self) -> PyResult<String> {
+        let definition = self.definition.as_ref().ok_or_else(|| {
+            PyValueError::new_err(
+                "This TextAnalyzer was not built with a TextAnalyzerBuilder and cannot be serialized.",
+            )
+        })?;
+        serde_json::to_string(definition).map_err(to_pyerr)
+    }
+
+    /// Build an analyzer from a JSON definition, as produced by `to_json()`.
+    ///
+    /// Example:
+    ///
+    /// ```python
+    /// analyzer = TextAnalyzer.from_json(
+    ///     '{"tokenizer": {"type": "simple"},'
+    ///     ' "filters": [{"type": "lowercase"}]}'
+    /// )
+    /// ```
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<TextAnalyzer> {
+        let definition: AnalyzerDefinition =
+            serde_json::from_str(json).map_err(to_pyerr)?;
+        TextAnalyzer::from_definition(definition)
+    }
+}
+
+impl TextAnalyzer {
+    pub(crate) fn from_definition(
+        definition: AnalyzerDefinition,
+    ) -> PyResult<TextAnalyzer> {
+        Ok(TextAnalyzer {
+            analyzer: definition.build()?,
+            definition: Some(definition),
+        })
+    }
 }
 
 /// Tantivy's TextAnalyzerBuilder
@@ -298,51 +571,19 @@ impl TextAnalyzer {
 #[pyclass]
 pub struct TextAnalyzerBuilder {
     builder: Option<tvt::TextAnalyzerBuilder>,
+    definition: AnalyzerDefinition,
 }
 
 #[pymethods]
 impl TextAnalyzerBuilder {
     #[new]
     fn new(tokenizer: &Tokenizer) -> PyResult<Self> {
-        let builder: tvt::TextAnalyzerBuilder = match tokenizer {
-            Tokenizer::_Raw {} => {
-                tvt::TextAnalyzer::builder(tvt::RawTokenizer::default())
-                    .dynamic()
-            }
-            Tokenizer::_Simple {} => {
-                tvt::TextAnalyzer::builder(tvt::SimpleTokenizer::default())
-                    .dynamic()
-            }
-            Tokenizer::_Whitespace {} => {
-                tvt::TextAnalyzer::builder(tvt::WhitespaceTokenizer::default())
-                    .dynamic()
-            }
-            Tokenizer::_Regex { pattern } => tvt::TextAnalyzer::builder(
-                tvt::RegexTokenizer::new(pattern).map_err(|e| {
-                    PyErr::new::<PyValueError, _>(format!(
-                        "Invalid regex pattern: {}",
-                        e
-                    ))
-                })?, // tvt::RegexTokenizer::new(pattern) .unwrap(),
-            )
-            .dynamic(),
-            Tokenizer::_Ngram {
-                min_gram,
-                max_gram,
-                prefix_only,
-            } => tvt::TextAnalyzer::builder(
-                tvt::NgramTokenizer::new(*min_gram, *max_gram, *prefix_only)
-                    .unwrap(),
-            )
-            .dynamic(),
-            Tokenizer::_Facet {} => {
-                tvt::TextAnalyzer::builder(tvt::FacetTokenizer::default())
-                    .dynamic()
-            }
-        };
-
         Ok(TextAnalyzerBuilder {
-            builder: Some(builder.dynamic()),
+            builder: Some(tokenizer_builder(tokenizer)?),
+            definition: AnalyzerDefinition {
+                tokenizer: tokenizer.clone(),
+                filters: Vec::new(),
+            },
         })
     }
 
@@ -356,59 +597,11 @@ impl TextAnalyzerBuilder {
     /// Note: The builder is _not_ mutated in-place.
     fn filter(&mut self, filter: &Filter) -> PyResult<Self> {
         if let Some(builder) = self.builder.take() {
-            let new_builder: tvt::TextAnalyzerBuilder = match filter {
-                Filter::_AlphaNumOnly {} => {
-                    builder.filter_dynamic(tvt::AlphaNumOnlyFilter {})
-                }
-                Filter::_AsciiFolding {} => {
-                    builder.filter_dynamic(tvt::AsciiFoldingFilter)
-                }
-                Filter::_LowerCaser {} => {
-                    builder.filter_dynamic(tvt::LowerCaser)
-                }
-                Filter::_RemoveLong { length_limit } => builder.filter_dynamic(
-                    tvt::RemoveLongFilter::limit(*length_limit),
-                ),
-                Filter::_Stemmer { language } => {
-                    match parse_language(language) {
-                        Ok(lang) => {
-                            builder.filter_dynamic(tvt::Stemmer::new(lang))
-                        }
-                        Err(e) => {
-                            return Err(PyErr::new::<
-                                pyo3::exceptions::PyValueError,
-                                _,
-                            >(e))
-                        }
-                    }
-                }
-                Filter::_StopWord { language } => {
-                    match parse_language(language) {
-                        Ok(lang) => builder.filter_dynamic(
-                            tvt::StopWordFilter::new(lang).unwrap(),
-                        ),
-                        Err(e) => {
-                            return Err(PyErr::new::<
-                                pyo3::exceptions::PyValueError,
-                                _,
-                            >(e))
-                        }
-                    }
-                }
-                Filter::_CustomStopWord { stopwords } => builder
-                    .filter_dynamic(tvt::StopWordFilter::remove(
-                        stopwords.clone(),
-                    )),
-                Filter::_SplitCompound { constituent_words } => builder
-                    .filter_dynamic(
-                        tvt::SplitCompoundWords::from_dictionary(
-                            constituent_words,
-                        )
-                        .unwrap(),
-                    ),
-            };
+            let mut definition = self.definition.clone();
+            definition.filters.push(filter.clone());
             Ok(TextAnalyzerBuilder {
-                builder: Some(new_builder),
+                builder: Some(add_filter(builder, filter)?),
+                definition,
             })
         } else {
             Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
@@ -428,6 +621,7 @@ impl TextAnalyzerBuilder {
         if let Some(builder) = self.builder.take() {
             Ok(TextAnalyzer {
                 analyzer: builder.build(),
+                definition: Some(self.definition.clone()),
             })
         } else {
             Err(PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(
@@ -436,3 +630,109 @@ impl TextAnalyzerBuilder {
         }
     }
 }
+
+fn tokenizer_builder(
+    tokenizer: &Tokenizer,
+) -> PyResult<tvt::TextAnalyzerBuilder> {
+    let builder: tvt::TextAnalyzerBuilder = match tokenizer {
+        Tokenizer::_Raw {} => {
+            tvt::TextAnalyzer::builder(tvt::RawTokenizer::default()).dynamic()
+        }
+        Tokenizer::_Simple {} => {
+            tvt::TextAnalyzer::builder(tvt::SimpleTokenizer::default())
+                .dynamic()
+        }
+        Tokenizer::_Whitespace {} => {
+            tvt::TextAnalyzer::builder(tvt::WhitespaceTokenizer::default())
+                .dynamic()
+        }
+        Tokenizer::_Regex { pattern } => tvt::TextAnalyzer::builder(
+            tvt::RegexTokenizer::new(pattern).map_err(|e| {
+                PyErr::new::<PyValueError, _>(format!(
+                    "Invalid regex pattern: {}",
+                    e
+                ))
+            })?, // tvt::RegexTokenizer::new(pattern) .unwrap(),
+        )
+        .dynamic(),
+        Tokenizer::_Ngram {
+            min_gram,
+            max_gram,
+            prefix_only,
+        } => tvt::TextAnalyzer::builder(
+            tvt::NgramTokenizer::new(*min_gram, *max_gram, *prefix_only)
+                .map_err(|e| {
+                    PyErr::new::<PyValueError, _>(format!(
+                        "Invalid ngram tokenizer: {}",
+                        e
+                    ))
+                })?,
+        )
+        .dynamic(),
+        Tokenizer::_Facet {} => {
+            tvt::TextAnalyzer::builder(tvt::FacetTokenizer::default()).dynamic()
+        }
+    };
+    Ok(builder.dynamic())
+}
+
+fn add_filter(
+    builder: tvt::TextAnalyzerBuilder,
+    filter: &Filter,
+) -> PyResult<tvt::TextAnalyzerBuilder> {
+    let builder: tvt::TextAnalyzerBuilder = match filter {
+        Filter::_AlphaNumOnly {} => {
+            builder.filter_dynamic(tvt::AlphaNumOnlyFilter {})
+        }
+        Filter::_AsciiFolding {} => {
+            builder.filter_dynamic(tvt::AsciiFoldingFilter)
+        }
+        Filter::_LowerCaser {} => builder.filter_dynamic(tvt::LowerCaser),
+        Filter::_RemoveLong { length_limit } => {
+            builder.filter_dynamic(tvt::RemoveLongFilter::limit(*length_limit))
+        }
+        Filter::_Stemmer { language } => match parse_language(language) {
+            Ok(lang) => builder.filter_dynamic(tvt::Stemmer::new(lang)),
+            Err(e) => {
+                return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(e))
+            }
+        },
+        Filter::_StopWord { language } => {
+            let stopword_filter = parse_language(language)
+                .and_then(|lang| {
+                    tvt::StopWordFilter::new(lang).ok_or_else(|| {
+                        format!(
+                            "No builtin stop word list for language: {}",
+                            language
+                        )
+                    })
+                })
+                .map_err(PyErr::new::<PyValueError, _>)?;
+            builder.filter_dynamic(stopword_filter)
+        }
+        Filter::_CustomStopWord { stopwords } => builder
+            .filter_dynamic(tvt::StopWordFilter::remove(stopwords.clone())),
+        Filter::_SplitCompound { constituent_words } => builder.filter_dynamic(
+            tvt::SplitCompoundWords::from_dictionary(constituent_words)
+                .map_err(|e| {
+                    PyErr::new::<PyValueError, _>(format!(
+                        "Invalid compound words dictionary: {}",
+                        e
+                    ))
+                })?,
+        ),
+        Filter::_EdgeNgram {
+            min_gram,
+            max_gram,
+            preserve_original,
+        } => builder.filter_dynamic(
+            EdgeNgramFilter::new(*min_gram, *max_gram, *preserve_original)
+                .map_err(PyErr::new::<PyValueError, _>)?,
+        ),
+        Filter::_Synonym { synonyms } => builder.filter_dynamic(
+            SynonymFilter::new(synonyms)
+                .map_err(PyErr::new::<PyValueError, _>)?,
+        ),
+    };
+    Ok(builder)
+}
@@ -1,13 +1,123 @@
 import datetime
+import decimal
+import ipaddress
 from enum import Enum
 from types import TracebackType
-from typing import Any, Optional, Sequence, TypeAlias, TypeVar, Union
+from typing import Any, Callable, Iterable, Optional, Protocol, Sequence, TypeAlias, TypeVar, Union
 from typing_extensions import Self
 
 
-class Schema:
+class TantivyError(ValueError):
+    pass
+
+class LockBusyError(TantivyError):
+    pass
+
+class IndexCorruptedError(TantivyError):
+    pass
+
+class DataCorruption(IndexCorruptedError):
+    pass
+
+class IncompatibleIndexError(TantivyError):
     pass
 
+class SchemaError(TantivyError):
+    pass
+
+class IoError(TantivyError):
+    def __init__(self, message: str, errno: Optional[int] = None) -> None:
+        pass
+
+    @property
+    def errno(self) -> Optional[int]:
+        pass
+
+class Schema:
+    def fields(self) -> list[FieldEntry]:
+        pass
+
+    def get_field_entry(self, field_name: str) -> FieldEntry:
+        pass
+
+    def to_json(self) -> str:
+        pass
+
+    @staticmethod
+    def from_json(json: str) -> Schema:
+        pass
+
+    def validate(
+        self,
+        document: Document | dict[str, Any],
+        single_valued: Optional[Sequence[str]] = None,
+    ) -> list[ValidationProblem]:
+        pass
+
+
+class FieldEntry:
+    @property
+    def name(self) -> str:
+        pass
+
+    @property
+    def field_type(self) -> FieldType:
+        pass
+
+    @property
+    def stored(self) -> bool:
+        pass
+
+    @property
+    def indexed(self) -> bool:
+        pass
+
+    @property
+    def fast(self) -> bool:
+        pass
+
+    @property
+    def fieldnorms(self) -> bool:
+        pass
+
+    @property
+    def tokenizer(self) -> Optional[str]:
+        pass
+
+    @property
+    def index_option(self) -> Optional[str]:
+        pass
+
+    @property
+    def fast_tokenizer(self) -> Optional[str]:
+        pass
+
+    @property
+    def expand_dots(self) -> Optional[bool]:
+        pass
+
+    @property
+    def coerce(self) -> Optional[bool]:
+        pass
+
+    @property
+    def precision(self) -> Optional[str]:
+        pass
+
+
+class ValidationProblem:
+    @property
+    def field_name(self) -> str:
+        pass
+
+    @property
+    def kind(self) -> str:
+        pass
+
+    @property
+    def message(self) -> str:
+        pass
+
 
 class SchemaBuilder:
     @staticmethod
@@ -21,6 +131,9 @@ class SchemaBuilder:
         fast: bool = False,
         tokenizer_name: str = "default",
         index_option: str = "position",
+        indexed: bool = True,
+        fieldnorms: bool = True,
+        fast_tokenizer_name: Optional[str] = None,
     ) -> SchemaBuilder:
         pass
 
@@ -30,6 +143,8 @@ class SchemaBuilder:
         stored: bool = False,
         indexed: bool = False,
         fast: bool = False,
+        fieldnorms: bool = False,
+        coerce: bool = False,
     ) -> SchemaBuilder:
         pass
 
@@ -39,6 +154,8 @@ class SchemaBuilder:
         stored: bool = False,
         indexed: bool = False,
         fast: bool = False,
+        fieldnorms: bool = False,
+        coerce: bool = False,
     ) -> SchemaBuilder:
         pass
 
@@ -48,6 +165,8 @@ class SchemaBuilder:
         stored: bool = False,
         indexed: bool = False,
         fast: bool = False,
+        fieldnorms: bool = False,
+        coerce: bool = False,
     ) -> SchemaBuilder:
         pass
 
@@ -57,6 +176,8 @@ class SchemaBuilder:
         stored: bool = False,
         indexed: bool = False,
         fast: bool = False,
+        fieldnorms: bool = False,
+        coerce: bool = False,
     ) -> SchemaBuilder:
         pass
 
@@ -66,6 +187,7 @@ class SchemaBuilder:
         stored: bool = False,
         indexed: bool = False,
         fast: bool = False,
+        precision: str = "seconds",
     ) -> SchemaBuilder:
         pass
 
@@ -83,6 +205,8 @@ class SchemaBuilder:
     def add_facet_field(
         self,
         name: str,
+        stored: bool = False,
+        fast: bool = True,
     ) -> SchemaBuilder:
         pass
 
@@ -105,6 +229,17 @@ class SchemaBuilder:
     ) -> SchemaBuilder:
         pass
 
+    @staticmethod
+    def infer(
+        samples: Iterable[dict[str, Any]],
+        overrides: Optional[dict[str, dict[str, Any]]] = None,
+    ) -> SchemaBuilder:
+        pass
+
+    @staticmethod
+    def from_dataclass(cls: type) -> SchemaBuilder:
+        pass
+
     def build(self) -> Schema:
         pass
 
@@ -168,7 +303,9 @@ class Document:
     def add_boolean(self, field_name: str, value: bool) -> None:
         pass
 
-    def add_date(self, field_name: str, value: datetime.datetime) -> None:
+    def add_date(
+        self, field_name: str, value: datetime.datetime | datetime.date
+    ) -> None:
         pass
 
     def add_facet(self, field_name: str, facet: Facet) -> None:
@@ -180,7 +317,11 @@ class Document:
     def add_json(self, field_name: str, value: Any) -> None:
         pass
 
-    def add_ip_addr(self, field_name: str, ip_addr: str) -> None:
+    def add_ip_addr(
+        self,
+        field_name: str,
+        ip_addr: str | ipaddress.IPv4Address | ipaddress.IPv6Address,
+    ) -> None:
         pass
 
     @property
@@ -218,7 +359,17 @@ class FieldType(Enum):
 
 
 _RangeType = TypeVar(
-    "_RangeType", bound=int | float | datetime.datetime | bool | str | bytes
+    "_RangeType",
+    bound=int
+    | float
+    | decimal.Decimal
+    | datetime.datetime
+    | datetime.date
+    | bool
+    | str
+    | bytes
+    | ipaddress.IPv4Address
+    | ipaddress.IPv6Address,
 )
 
 DocumentFieldInput: TypeAlias = dict[str, Any | list[Any]]
@@ -370,6 +521,27 @@ class Query:
     def explain(self, searcher: Searcher, doc_address: DocAddress) -> Explanation:
         pass
 
+    @property
+    def kind(self) -> str:
+        pass
+
+    @property
+    def boost(self) -> Optional[float]:
+        pass
+
+    def clauses(self) -> list[tuple[Occur, Query]]:
+        pass
+
+    def terms(self, searcher: Optional[Searcher] = None) -> list[tuple[str, Any]]:
+        pass
+
+    def to_dict(self) -> dict[str, Any]:
+        pass
+
+    @staticmethod
+    def from_dict(schema: Schema, query_dict: dict[str, Any]) -> Query:
+        pass
+
 
 class Explanation:
     def to_json(self) -> str:
@@ -453,6 +625,135 @@ class Searcher:
         pass
 
 
+class MultiSearchResult:
+    @property
+    def hits(self) -> list[tuple[Any, int, DocAddress]]:
+        pass
+
+    @property
+    def count(self) -> Optional[int]:
+        pass
+
+
+class MultiSearcher:
+    def __new__(cls, searchers: list[Searcher]) -> MultiSearcher:
+        pass
+
+    def search(
+        self,
+        query: Query,
+        limit: int = 10,
+        count: bool = True,
+        order_by_field: Optional[str] = None,
+        offset: int = 0,
+        order: Order = Order.Desc,
+        weight_by_field: str | None = None,
+    ) -> MultiSearchResult:
+        pass
+
+    def doc(self, shard: int, doc_address: DocAddress) -> Document:
+        pass
+
+    @property
+    def num_docs(self) -> int:
+        pass
+
+    @property
+    def num_shards(self) -> int:
+        pass
+
+
+class Percolator:
+    def __new__(cls, schema: Schema) -> Percolator:
+        pass
+
+    def add(self, query_id: str, query: Query) -> None:
+        pass
+
+    def remove(self, query_id: str) -> bool:
+        pass
+
+    def register_tokenizer(self, name: str, text_analyzer: TextAnalyzer) -> None:
+        pass
+
+    def match(self, doc: Document) -> list[str]:
+        pass
+
+    def __len__(self) -> int:
+        pass
+
+    def __contains__(self, query_id: str) -> bool:
+        pass
+
+
+class JsonFileReport:
+    @property
+    def num_added(self) -> int:
+        pass
+
+    @property
+    def num_rejected(self) -> int:
+        pass
+
+    @property
+    def errors(self) -> list[tuple[int, str]]:
+        pass
+
+class VerificationReport:
+    @property
+    def num_segments(self) -> int:
+        pass
+
+    @property
+    def num_files(self) -> int:
+        pass
+
+    @property
+    def missing_files(self) -> list[str]:
+        pass
+
+    @property
+    def damaged_files(self) -> list[str]:
+        pass
+
+    @property
+    def is_valid(self) -> bool:
+        pass
+
+class WriterStats:
+    @property
+    def uncommitted_docs(self) -> int:
+        pass
+
+    @property
+    def uncommitted_operations(self) -> int:
+        pass
+
+    @property
+    def heap_size(self) -> int:
+        pass
+
+    @property
+    def num_threads(self) -> Optional[int]:
+        pass
+
+    @property
+    def memory_budget_per_thread(self) -> Optional[int]:
+        pass
+
+    @property
+    def memory_arena_usage(self) -> int:
+        pass
+
+    @property
+    def num_segments(self) -> int:
+        pass
+
+    @property
+    def num_merging_segments(self) -> int:
+        pass
+
+
 class IndexWriter:
     def add_document(self, doc: Document) -> int:
         pass
@@ -460,6 +761,9 @@ class IndexWriter:
     def add_json(self, json: str) -> int:
         pass
 
+    def add_json_file(self, path: str, on_error: str = "raise") -> JsonFileReport:
+        pass
+
     def commit(self) -> int:
         pass
 
@@ -476,6 +780,13 @@ class IndexWriter:
     def commit_opstamp(self) -> int:
         pass
 
+    @property
+    def last_auto_commit_error(self) -> Optional[str]:
+        pass
+
+    def stats(self) -> WriterStats:
+        pass
+
     def delete_documents(self, field_name: str, field_value: Any) -> int:
         """Deprecated alias of ``delete_documents_by_term``; emits a
         ``DeprecationWarning``. Use ``delete_documents_by_term`` or
@@ -503,17 +814,53 @@ class IndexWriter:
         pass
 
 
+class _DirectoryStorage(Protocol):
+    def read_range(self, path: str, start: int, end: int) -> bytes: ...
+    def file_size(self, path: str) -> int: ...
+    def write(self, path: str, data: bytes) -> None: ...
+    def atomic_write(self, path: str, data: bytes) -> None: ...
+    def delete(self, path: str) -> None: ...
+
 class Index:
     def __new__(
-        cls, schema: Schema, path: Optional[str] = None, reuse: bool = True
+        cls,
+        schema: Schema,
+        path: Optional[str] = None,
+        reuse: bool = True,
+        directory: Optional[_DirectoryStorage] = None,
+        read_cache_size: int = 0,
     ) -> Index:
         pass
 
     @staticmethod
-    def open(path: str) -> Index:
+    def open(
+        path: Optional[str] = None,
+        directory: Optional[_DirectoryStorage] = None,
+        read_cache_size: int = 0,
+        read_only: bool = False,
+        preload: list[str] = [],
+    ) -> Index:
+        pass
+
+    def preload(self, components: list[str]) -> dict[str, int]:
         pass
 
-    def writer(self, heap_size: int = 128_000_000, num_threads: int = 0) -> IndexWriter:
+    @property
+    def preloaded_bytes(self) -> dict[str, int]:
+        pass
+
+    @property
+    def read_only(self) -> bool:
+        pass
+
+    def writer(
+        self,
+        heap_size: int = 128_000_000,
+        num_threads: int = 0,
+        auto_commit_docs: Optional[int] = None,
+        auto_commit_interval: Optional[float] = None,
+        auto_commit_payload: Optional[Callable[[], Optional[str]]] = None,
+    ) -> IndexWriter:
         pass
 
     def config_reader(
@@ -532,6 +879,34 @@ class Index:
     def is_compatible(path: str) -> bool:
         pass
 
+    @staticmethod
+    def validate(path: str) -> VerificationReport:
+        pass
+
+    def verify(self) -> VerificationReport:
+        pass
+
+    def snapshot(self, dest_path: str) -> int:
+        pass
+
+    def save(self, path: str) -> None:
+        pass
+
+    def to_bytes(self) -> bytes:
+        pass
+
+    @staticmethod
+    def from_bytes(data: bytes) -> Index:
+        pass
+
+    @staticmethod
+    def merge_from(
+        indexes: list[Index],
+        output_path: str,
+        filter_query: Optional[Query] = None,
+    ) -> Index:
+        pass
+
     @property
     def schema(self) -> Schema:
         pass
@@ -561,12 +936,24 @@ class Index:
     ) -> tuple[Query, list[Any]]:
         pass
 
+    def add_fields(self, fields: SchemaBuilder) -> None:
+        pass
+
+    def tokenizer_for_field(self, field_name: str) -> TextAnalyzer:
+        pass
+
     def register_tokenizer(
-        self, name: str, text_analyzer: TextAnalyzer
+        self,
+        name: str,
+        analyzer: TextAnalyzer,
+        persist: Optional[bool] = None,
     ) -> None: ...
 
     def register_fast_field_tokenizer(
-        self, name: str, text_analyzer: TextAnalyzer
+        self,
+        name: str,
+        analyzer: TextAnalyzer,
+        persist: Optional[bool] = None,
     ) -> None: ...
 
 
@@ -666,11 +1053,55 @@ class Filter:
     def split_compound(constituent_words: list[str]) -> Filter:
         pass
 
+    @staticmethod
+    def edge_ngram(
+        min_gram: int = 1, max_gram: int = 2, preserve_original: bool = False
+    ) -> Filter:
+        pass
+
+    @staticmethod
+    def synonym(
+        synonyms: dict[str, list[str]] | list[list[str]],
+    ) -> Filter:
+        pass
+
+
+class Token:
+    @property
+    def text(self) -> str:
+        pass
+
+    @property
+    def offset_from(self) -> int:
+        pass
+
+    @property
+    def offset_to(self) -> int:
+        pass
+
+    @property
+    def position(self) -> int:
+        pass
+
+    @property
+    def position_length(self) -> int:
+        pass
+
 
 class TextAnalyzer:
     def analyze(self, text: str) -> list[str]:
         pass
 
+    def analyze_tokens(self, text: str) -> list[Token]:
+        pass
+
+    def to_json(self) -> str:
+        pass
+
+    @staticmethod
+    def from_json(json: str) -> TextAnalyzer:
+        pass
+
 
 class TextAnalyzerBuilder:
     def __init__(self, tokenizer: Tokenizer):
@@ -5,6 +5,8 @@ import datetime
 import json
 import pickle
 from itertools import groupby
+from pathlib import Path
+from typing import Any
 
 import pytest
 
@@ -579,6 +581,152 @@ class TestClass(object):
         with pytest.raises(ValueError):
             bad = Document.from_dict({"json": "hello"}, schema)
 
+    def test_doc_native_python_types(self):
+        import decimal
+        import ipaddress
+        import uuid
+
+        schema = (
+            SchemaBuilder()
+            .add_text_field("id", stored=True, tokenizer_name="raw")
+            .add_bytes_field("raw_id", stored=True, indexed=True)
+            .add_ip_addr_field("ip", stored=True, indexed=True, fast=True)
+            .add_date_field("day", stored=True, indexed=True, fast=True)
+            .add_float_field("price", stored=True, indexed=True, fast=True)
+            .add_integer_field("count", stored=True, indexed=True)
+            .build()
+        )
+        book_id = uuid.UUID("12345678-1234-5678-1234-567812345678")
+        ip = ipaddress.IPv4Address("10.0.0.1")
+        index = Index(schema)
+        writer = index.writer()
+        writer.add_document(
+            Document.from_dict(
+                {
+                    "id": book_id,
+                    "raw_id": book_id,
+                    "ip": [ip, ipaddress.IPv6Address("2001:db8::1")],
+                    "day": datetime.date(2024, 2, 29),
+                    "price": decimal.Decimal("9.99"),
+                    "count": decimal.Decimal("3"),
+                },
+                schema,
+            )
+        )
+        writer.commit()
+        index.reload()
+        searcher = index.searcher()
+
+        def count(query):
+            return searcher.search(query).count
+
+        assert count(Query.term_query(schema, "id", book_id)) == 1
+        assert count(Query.term_query(schema, "raw_id", book_id)) == 1
+        assert count(Query.term_query(schema, "ip", ip)) == 1
+        assert count(Query.term_query(schema, "count", decimal.Decimal(3))) == 1
+        assert count(
+            Query.term_query(schema, "day", datetime.date(2024, 2, 29))
+        ) == 1
+        assert count(
+            Query.range_query(
+                schema,
+                "ip",
+                FieldType.IpAddr,
+                ipaddress.IPv4Address("10.0.0.0"),
+                ipaddress.IPv4Address("10.0.255.255"),
+            )
+        ) == 1
+        assert count(
+            Query.range_query(
+                schema,
+                "day",
+                FieldType.Date,
+                datetime.date(2024, 2, 1),
+                datetime.date(2024, 3, 1),
+            )
+        ) == 1
+        assert count(
+            Query.range_query(
+                schema,
+                "price",
+                FieldType.Float,
+                decimal.Decimal("9.5"),
+                decimal.Decimal("10"),
+            )
+        ) == 1
+
+        doc = searcher.doc(searcher.search(Query.all_query()).hits[0][1])
+        assert doc.to_dict() == {
+            "id": [str(book_id)],
+            "raw_id": [book_id.bytes],
+            "ip": [ip, ipaddress.IPv6Address("2001:db8::1")],
+            "day": [
+                datetime.datetime(2024, 2, 29, tzinfo=datetime.timezone.utc)
+            ],
+            "price": [9.99],
+            "count": [3],
+        }
+
+        with pytest.raises(ValueError):
+            Document.from_dict({"count": decimal.Decimal("1.5")}, schema)
+
+    def test_doc_native_python_types_without_schema(self):
+        import ipaddress
+        import uuid
+
+        book_id = uuid.uuid4()
+        doc = Document(
+            id=book_id,
+            ip=ipaddress.IPv6Address("::1"),
+            day=datetime.date(2024, 1, 1),
+        )
+        doc.add_ip_addr("other_ip", ipaddress.IPv4Address("127.0.0.1"))
+        doc.add_date("other_day", datetime.date(2024, 1, 2))
+        assert doc.to_dict() == {
+            "id": [str(book_id)],
+            "ip": [ipaddress.IPv6Address("::1")],
+            "other_ip": [ipaddress.IPv4Address("127.0.0.1")],
+            "day": [datetime.datetime(2024, 1, 1, tzinfo=datetime.timezone.utc)],
+            "other_day": [
+                datetime.datetime(2024, 1, 2, tzinfo=datetime.timezone.utc)
+            ],
+        }
+
+    def test_doc_numpy_scalars(self):
+        np = pytest.importorskip("numpy")
+
+        schema = (
+            SchemaBuilder()
+            .add_unsigned_field("views", stored=True, indexed=True)
+            .add_float_field("score", stored=True)
+            .add_boolean_field("available", stored=True, indexed=True)
+            .build()
+        )
+        doc = Document.from_dict(
+            {
+                "views": np.uint32(7),
+                "score": np.float32(0.5),
+                "available": np.bool_(True),
+            },
+            schema,
+        )
+        assert doc.to_dict() == {
+            "views": [7],
+            "score": [0.5],
+            "available": [True],
+        }
+        assert Document(available=np.bool_(False)).to_dict() == {
+            "available": [False]
+        }
+
+        index = Index(schema)
+        writer = index.writer()
+        writer.add_document(doc)
+        writer.commit()
+        index.reload()
+        query = Query.term_query(schema, "views", np.int64(7))
+        assert index.searcher().search(query).count == 1
+
     def test_search_result_eq(self, ram_index, spanish_index):
         eng_index = ram_index
         eng_query = eng_index.parse_query("sea whale", ["title", "body"])
@@ -632,6 +780,38 @@ class TestClass(object):
         result = index.searcher().search(Query.all_query())
         assert len(result.hits) == 1
 
+    def test_index_writer_shared_between_threads(self):
+        from concurrent.futures import ThreadPoolExecutor
+
+        schema = (
+            SchemaBuilder()
+            .add_text_field("title", stored=True, tokenizer_name="raw")
+            .add_integer_field("producer", indexed=True)
+            .build()
+        )
+        index = Index(schema)
+        writer = index.writer()
+
+        def produce(producer):
+            for i in range(200):
+                writer.add_document(
+                    Document.from_dict(
+                        {"title": f"{producer}-{i}", "producer": producer},
+                        schema,
+                    )
+                )
+                if i % 50 == 0:
+                    writer.commit()
+            writer.delete_documents_by_term("title", f"{producer}-0")
+
+        with ThreadPoolExecutor(max_workers=8) as executor:
+            list(executor.map(produce, range(8)))
+        writer.commit()
+        writer.wait_merging_threads()
+
+        index.reload()
+        assert index.searcher().num_docs == 8 * 199
+
     def test_simple_search_facet(self):
         schema = (
             tantivy.SchemaBuilder()
@@ -659,6 +839,62 @@ class TestClass(object):
         assert len(result.hits) == 1
 
 
+    def test_stored_facet_field(self):
+        schema = (
+            tantivy.SchemaBuilder()
+            .add_text_field("title", stored=True)
+            .add_facet_field("category", stored=True)
+            .add_facet_field("tag")
+            .build()
+        )
+        assert schema.get_field_entry("category").stored
+        assert schema.get_field_entry("category").fast
+        assert not schema.get_field_entry("tag").stored
+
+        index = Index(schema)
+        writer = index.writer(15_000_000, 1)
+        for title, category in [
+            ("Moby Dick", "/books/fiction"),
+            ("Leaves of Grass", "/books/poetry"),
+            ("Kind of Blue", "/music/jazz"),
+        ]:
+            doc = Document(title=title)
+            doc.add_facet("category", tantivy.Facet.from_string(category))
+            doc.add_facet("tag", tantivy.Facet.from_string("/all"))
+            writer.add_document(doc)
+        writer.commit()
+        index.reload()
+        searcher = index.searcher()
+
+        query = index.parse_query("moby", ["title"])
+        _, address = searcher.search(query, 1).hits[0]
+        doc = searcher.doc(address)
+        assert doc["category"] == [tantivy.Facet.from_string("/books/fiction")]
+        assert "tag" not in doc.to_dict()
+        round_tripped = Document.from_dict(doc.to_dict(), schema)
+        assert round_tripped["category"][0].to_path_str() == "/books/fiction"
+
+        result = searcher.aggregate(
+            Query.all_query(),
+            {
+                "categories": {
+                    "terms": {"field": "category"},
+                    "aggs": {"tags": {"terms": {"field": "tag"}}},
+                }
+            },
+        )
+        buckets = result["categories"]["buckets"]
+        assert sorted(b["key"] for b in buckets) == [
+            "/books/fiction",
+            "/books/poetry",
+            "/music/jazz",
+        ]
+        assert buckets[0]["tags"]["buckets"][0]["key"] == "/all"
+
+        with pytest.raises(ValueError, match="always fast"):
+            tantivy.SchemaBuilder().add_facet_field("category", fast=False)
+
+
 class TestUpdateClass(object):
     def test_delete_update(self, ram_index):
         query = ram_index.parse_query("Frankenstein", ["title"])
@@ -703,6 +939,481 @@ class TestFromDiskClass(object):
         with pytest.raises(ValueError):
             Index.is_compatible(str(tmp_path / "does-not-exist"))
 
+    def test_verify(self, dir_index):
+        index_dir = Path(dir_index[0])
+        index = Index.open(str(index_dir))
+        report = index.verify()
+        assert report.is_valid
+        assert report.num_segments >= 1
+        assert report.num_files >= report.num_segments
+        assert Index.validate(str(index_dir)).num_files == report.num_files
+
+        paths = sorted(index_dir.iterdir())
+        store = next(path for path in paths if path.suffix == ".store")
+        # Overwritten in place, truncating a memory-mapped file is unsafe.
+        with store.open("r+b") as f:
+            first_byte = f.read(1)[0]
+            f.seek(0)
+            f.write(bytes([first_byte ^ 0xFF]))
+        next(path for path in paths if path.suffix == ".term").unlink()
+        report = Index.validate(str(index_dir))
+        assert not report.is_valid
+        assert report.damaged_files == [store.name]
+        assert len(report.missing_files) == 1
+        assert report.missing_files[0].endswith(".term")
+
+        (index_dir / "meta.json").write_text("{")
+        report = Index.validate(str(index_dir))
+        assert report.damaged_files == ["meta.json"]
+
+    def test_snapshot(self, tmp_path):
+        schema = SchemaBuilder().add_integer_field("id", indexed=True).build()
+        (tmp_path / "index").mkdir()
+        index = Index(schema, str(tmp_path / "index"))
+        index.config_reader("Manual")
+        writer = index.writer()
+        for i in range(5):
+            writer.add_document(Document(id=i))
+        writer.commit()
+        writer.delete_documents_by_term("id", 0)
+        opstamp = writer.commit()
+        writer.add_document(Document(id=5))
+
+        # The reader still searches the initial empty commit.
+        with pytest.raises(ValueError, match="reload"):
+            index.snapshot(str(tmp_path / "snapshot"))
+        index.reload()
+        assert index.snapshot(str(tmp_path / "snapshot")) == opstamp
+        assert (tmp_path / "snapshot" / ".managed.json").exists()
+        writer.commit()
+        writer.garbage_collect_files()
+        writer.wait_merging_threads()
+
+        snapshot = Index.open(str(tmp_path / "snapshot"))
+        assert snapshot.searcher().num_docs == 4
+        assert snapshot.verify().is_valid
+
+        with pytest.raises(ValueError, match="already contains an index"):
+            index.snapshot(str(tmp_path / "snapshot"))
+
+        ram_index = Index(schema)
+        writer = ram_index.writer()
+        writer.add_document(Document(id=1))
+        writer.commit()
+        writer.wait_merging_threads()
+        ram_index.reload()
+        ram_index.snapshot(str(tmp_path / "ram_snapshot"))
+        assert Index.open(str(tmp_path / "ram_snapshot")).searcher().num_docs == 1
+
+    def test_to_bytes_and_save(self, tmp_path):
+        schema = SchemaBuilder().add_text_field("title", stored=True).build()
+        index = Index(schema)
+        writer = index.writer()
+        writer.add_document(Document(title="The Old Man and the Sea"))
+        writer.commit()
+        writer.add_document(Document(title="uncommitted"))
+
+        data = index.to_bytes()
+        assert isinstance(data, bytes)
+        copy = Index.from_bytes(pickle.loads(pickle.dumps(data)))
+        assert copy.searcher().num_docs == 1
+        query = copy.parse_query("sea", ["title"])
+        assert len(copy.searcher().search(query, 10).hits) == 1
+
+        copy_writer = copy.writer()
+        copy_writer.add_document(Document(title="Moby Dick"))
+        copy_writer.commit()
+        copy_writer.wait_merging_threads()
+        copy.reload()
+        assert copy.searcher().num_docs == 2
+
+        index.save(str(tmp_path / "saved"))
+        assert Index.open(str(tmp_path / "saved")).searcher().num_docs == 1
+        writer.rollback()
+        writer.wait_merging_threads()
+
+        with pytest.raises(ValueError, match="not a packed index"):
+            Index.from_bytes(data[:-1])
+        with pytest.raises(ValueError, match="not a packed index"):
+            Index.from_bytes(b"")
+
+    def test_merge_from(self, tmp_path):
+        schema = SchemaBuilder().add_text_field("title", stored=True).build()
+        indexes = []
+        for titles in (["The Old Man and the Sea", "Moby Dick"], ["The Sea Wolf"]):
+            index = Index(schema)
+            writer = index.writer()
+            for title in titles:
+                writer.add_document(Document(title=title))
+                writer.commit()
+            writer.wait_merging_threads()
+            indexes.append(index)
+
+        merged = Index.merge_from(indexes, str(tmp_path / "merged"))
+        assert merged.searcher().num_docs == 3
+        assert merged.searcher().num_segments == 1
+        query = merged.parse_query("sea", ["title"])
+        assert len(merged.searcher().search(query, 10).hits) == 2
+        assert Index.open(str(tmp_path / "merged")).searcher().num_docs == 3
+
+        pruned = Index.merge_from(
+            indexes, str(tmp_path / "pruned"), filter_query=query
+        )
+        searcher = pruned.searcher()
+        assert searcher.num_docs == 1
+        hit = searcher.search(Query.all_query(), 10).hits[0][1]
+        assert searcher.doc(hit)["title"] == ["Moby Dick"]
+
+        with pytest.raises(ValueError, match="already contains an index"):
+            Index.merge_from(indexes, str(tmp_path / "merged"))
+        with pytest.raises(ValueError, match="merged twice"):
+            Index.merge_from([indexes[0], indexes[0]], str(tmp_path / "twice"))
+        source_dir = tmp_path / "source"
+        source_dir.mkdir()
+        Index(schema, str(source_dir))
+        with pytest.raises(ValueError, match="merged twice"):
+            Index.merge_from(
+                [Index.open(str(source_dir)), Index.open(f"{source_dir}/")],
+                str(tmp_path / "twice"),
+            )
+        with pytest.raises(ValueError, match="At least one index"):
+            Index.merge_from([], str(tmp_path / "empty"))
+
+    def test_open_read_only_with_preload(self, dir_index):
+        index_dir, _ = dir_index
+        index = Index.open(
+            str(index_dir), read_only=True, preload=["postings", "fast", "store"]
+        )
+        assert index.read_only
+        assert set(index.preloaded_bytes) == {"postings", "fast", "store"}
+        assert index.preloaded_bytes["store"] > 0
+        assert index.searcher().num_docs == 3
+
+        with pytest.raises(ValueError, match="read-only"):
+            index.writer()
+        with pytest.raises(ValueError, match="read-only"):
+            index.add_fields(SchemaBuilder().add_text_field("new_field"))
+        analyzer = tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.raw()).build()
+        index.register_tokenizer("raw_copy", analyzer)
+        with pytest.raises(ValueError, match="read-only"):
+            index.register_tokenizer("raw_copy", analyzer, persist=True)
+
+        assert not Index.open(str(index_dir)).read_only
+        assert Index.open(str(index_dir)).preloaded_bytes == {}
+        assert index.preload(["terms"])["terms"] > 0
+        with pytest.raises(ValueError, match="Invalid preload component"):
+            index.preload(["everything"])
+
+    def test_error_classes(self, tmp_path):
+        schema = SchemaBuilder().add_integer_field("id", indexed=True).build()
+        index = Index(schema, str(tmp_path))
+        writer = index.writer()
+        with pytest.raises(tantivy.LockBusyError) as excinfo:
+            index.writer()
+        assert isinstance(excinfo.value, tantivy.TantivyError)
+        assert isinstance(excinfo.value, ValueError)
+        writer.add_document(Document(id=1))
+        writer.commit()
+        writer.wait_merging_threads()
+
+        with pytest.raises(tantivy.IoError) as excinfo:
+            Index.open(str(tmp_path / "does-not-exist"))
+        assert excinfo.value.errno is None
+
+        with pytest.raises(tantivy.IoError) as excinfo:
+            Index(schema, str(tmp_path / "index.file"))
+        assert "does not exist" in str(excinfo.value)
+
+        for path in tmp_path.iterdir():
+            if path.suffix == ".store":
+                path.unlink()
+        with pytest.raises(tantivy.IndexCorruptedError):
+            Index.open(str(tmp_path)).searcher()
+
+        meta = (tmp_path / "meta.json").read_text()
+        (tmp_path / "meta.json").write_text(meta[: len(meta) // 2])
+        with pytest.raises(tantivy.DataCorruption) as excinfo:
+            Index.open(str(tmp_path))
+        assert isinstance(excinfo.value, tantivy.IndexCorruptedError)
+
+        (tmp_path / "other").mkdir()
+        Index(schema, str(tmp_path / "other"))
+        invalid_schema = SchemaBuilder().add_text_field("🐱").build()
+        with pytest.raises(tantivy.SchemaError) as excinfo:
+            Index(invalid_schema, str(tmp_path / "other"), reuse=True)
+        assert isinstance(excinfo.value, ValueError)
+
+        error = tantivy.IoError("Disk full", 28)
+        assert str(error) == "Disk full"
+        assert error.errno == 28
+
+    def test_add_fields(self, tmp_path):
+        schema = SchemaBuilder().add_text_field("title", stored=True).build()
+        index = Index(schema, str(tmp_path))
+        writer = index.writer()
+        writer.add_document(Document(title="old document"))
+        writer.commit()
+        writer.wait_merging_threads()
+
+        index.add_fields(
+            SchemaBuilder()
+            .add_unsigned_field("views", stored=True, indexed=True, fast=True)
+            .add_text_field("body")
+        )
+        assert [f.name for f in index.schema.fields()] == ["title", "views", "body"]
+        assert index.schema.get_field_entry("title").fieldnorms
+        assert not index.schema.get_field_entry("body").fieldnorms
+
+        writer = index.writer()
+        writer.add_document(
+            Document.from_dict(
+                {"title": "new document", "views": 7, "body": "fresh"}, index.schema
+            )
+        )
+        writer.commit()
+        index.reload()
+
+        searcher = index.searcher()
+        assert searcher.num_docs == 2
+        query = Query.term_query(index.schema, "views", 7)
+        assert searcher.search(query).count == 1
+        query = index.parse_query("fresh", ["body"])
+        assert searcher.search(query).count == 1
+        # The old segment is still searchable on the existing fields.
+        query = index.parse_query("document", ["title"])
+        hits = searcher.search(query).hits
+        addrs = [addr for _, addr in hits]
+        assert sorted(searcher.fast_field_values("views", addrs), key=str) == [
+            7,
+            None,
+        ]
+
+        # Merging old and new segments keeps the documents intact.
+        for views in range(8):
+            writer.add_document(
+                Document.from_dict({"title": "more", "views": views}, index.schema)
+            )
+            writer.commit()
+        writer.wait_merging_threads()
+        index.reload()
+        searcher = index.searcher()
+        assert searcher.num_segments < 10
+        assert searcher.num_docs == 10
+        query = Query.term_query(index.schema, "views", 7)
+        assert searcher.search(query).count == 2
+
+        reopened = Index.open(str(tmp_path))
+        assert reopened.schema == index.schema
+        assert reopened.searcher().num_docs == 10
+        # The extended schema is needed to reuse the directory.
+        with pytest.raises(ValueError):
+            Index(schema, str(tmp_path), reuse=True)
+
+    def test_add_fields_errors(self, tmp_path):
+        schema = SchemaBuilder().add_text_field("title").build()
+        index = Index(schema, str(tmp_path))
+
+        with pytest.raises(ValueError, match="already defined"):
+            index.add_fields(SchemaBuilder().add_text_field("title"))
+
+        writer = index.writer()
+        with pytest.raises(ValueError, match="IndexWriter is open"):
+            index.add_fields(SchemaBuilder().add_text_field("body"))
+        writer.wait_merging_threads()
+
+        index.add_fields(SchemaBuilder().add_text_field("body"))
+        assert index.schema.get_field_entry("body").indexed
+
+    def test_add_json_file(self, tmp_path):
+        schema = (
+            SchemaBuilder()
+            .add_text_field("title", stored=True)
+            .add_unsigned_field("rank", stored=True, fast=True)
+            .build()
+        )
+        path = tmp_path / "docs.jsonl"
+        with open(path, "w") as f:
+            for rank in range(25_000):
+                f.write(json.dumps({"title": f"doc {rank}", "rank": rank}))
+                f.write("\n")
+            f.write("\n")
+
+        index = Index(schema)
+        writer = index.writer()
+        report = writer.add_json_file(str(path))
+        assert (report.num_added, report.num_rejected) == (25_000, 0)
+        assert report.errors == []
+        writer.commit()
+        index.reload()
+        searcher = index.searcher()
+        assert searcher.num_docs == 25_000
+
+        # The documents are added in the order of the lines.
+        hits = searcher.search(Query.all_query(), 3, order_by_field="rank").hits
+        assert [searcher.doc(addr)["rank"][0] for _, addr in hits] == [
+            24_999,
+            24_998,
+            24_997,
+        ]
+
+    def test_add_json_file_errors(self, tmp_path):
+        schema = SchemaBuilder().add_unsigned_field("rank", stored=True).build()
+        path = tmp_path / "docs.jsonl"
+        path.write_text(
+            '{"rank": 1}\n{"rank": -1}\nnot json\n\n{"rank": 2}\n'
+        )
+
+        index = Index(schema)
+        writer = index.writer()
+        report = writer.add_json_file(str(path), on_error="collect")
+        assert (report.num_added, report.num_rejected) == (2, 2)
+        assert [line for line, _ in report.errors] == [2, 3]
+
+        report = writer.add_json_file(str(path), on_error="skip")
+        assert (report.num_added, report.num_rejected) == (2, 2)
+        assert report.errors == []
+
+        with pytest.raises(ValueError, match="Line 2"):
+            writer.add_json_file(str(path))
+        writer.commit()
+        index.reload()
+        # With 'raise', only the documents before the invalid line are added.
+        assert index.searcher().num_docs == 5
+
+        with pytest.raises(ValueError, match="Invalid on_error"):
+            writer.add_json_file(str(path), on_error="ignore")
+        with pytest.raises(ValueError):
+            writer.add_json_file(str(tmp_path / "missing.jsonl"))
+
+    def test_writer_auto_commit(self, tmp_path):
+        import time
+
+        schema = SchemaBuilder().add_integer_field("id", stored=True).build()
+        index = Index(schema, str(tmp_path))
+
+        def wait_for(condition, timeout=30):
+            deadline = time.monotonic() + timeout
+            while not condition():
+                if time.monotonic() > deadline:
+                    return False
+                time.sleep(0.05)
+            return True
+
+        def num_docs():
+            index.reload()
+            return index.searcher().num_docs
+
+        writer = index.writer(auto_commit_docs=10)
+        for i in range(10):
+            writer.add_document(Document.from_dict({"id": i}, schema))
+        assert wait_for(lambda: num_docs() == 10)
+        for i in range(10, 15):
+            writer.add_document(Document.from_dict({"id": i}, schema))
+        # The auto-commit resets the count while holding the writer lock,
+        # the added documents are therefore all still uncommitted.
+        assert writer.stats().uncommitted_docs == 5
+        assert num_docs() == 10
+        writer.commit()
+        assert num_docs() == 15
+        writer.wait_merging_threads()
+
+        payloads = iter(range(100))
+        writer = index.writer(
+            auto_commit_interval=0.05,
+            auto_commit_payload=lambda: f"batch-{next(payloads)}",
+        )
+        writer.add_document(Document.from_dict({"id": 15}, schema))
+        assert wait_for(lambda: num_docs() == 16)
+        meta = json.loads((tmp_path / "meta.json").read_text())
+        assert meta["payload"] == "batch-0"
+        assert writer.last_auto_commit_error is None
+        writer.wait_merging_threads()
+
+    def test_writer_auto_commit_errors(self, tmp_path):
+        import time
+
+        schema = SchemaBuilder().add_integer_field("id", stored=True).build()
+        index = Index(schema, str(tmp_path))
+
+        def failing_payload():
+            raise RuntimeError("no payload")
+
+        writer = index.writer(
+            auto_commit_interval=0.05, auto_commit_payload=failing_payload
+        )
+        writer.add_document(Document.from_dict({"id": 1}, schema))
+        deadline = time.monotonic() + 30
+        while writer.last_auto_commit_error is None:
+            assert time.monotonic() < deadline
+            time.sleep(0.05)
+        assert "no payload" in writer.last_auto_commit_error
+        writer.rollback()
+        writer.wait_merging_threads()
+
+        with pytest.raises(ValueError, match="auto_commit_docs"):
+            index.writer(auto_commit_docs=0)
+        with pytest.raises(ValueError, match="auto_commit_interval"):
+            index.writer(auto_commit_interval=-1)
+        with pytest.raises(ValueError, match="callable"):
+            index.writer(auto_commit_docs=1, auto_commit_payload="payload")
+        with pytest.raises(ValueError, match="requires"):
+            index.writer(auto_commit_payload=lambda: "payload")
+
+    def test_writer_auto_commit_payload_cycle(self, tmp_path):
+        import gc
+
+        schema = SchemaBuilder().add_integer_field("id", stored=True).build()
+        index = Index(schema, str(tmp_path))
+
+        class Payload:
+            def __call__(self):
+                return str(self.writer.stats().uncommitted_docs)
+
+        payload = Payload()
+        payload.writer = index.writer(
+            auto_commit_interval=60, auto_commit_payload=payload
+        )
+        del payload
+        gc.collect()
+        # The index lock is released once the writer is collected, along
+        # with the payload hook referencing it.
+        index.writer().wait_merging_threads()
+
+    def test_writer_stats(self, tmp_path):
+        schema = SchemaBuilder().add_integer_field("id", indexed=True).build()
+        index = Index(schema, str(tmp_path))
+        writer = index.writer(heap_size=60_000_000, num_threads=2)
+
+        stats = writer.stats()
+        assert stats.uncommitted_docs == 0
+        assert stats.uncommitted_operations == 0
+        assert stats.heap_size == 60_000_000
+        assert stats.num_threads == 2
+        assert stats.memory_budget_per_thread == 30_000_000
+        assert stats.memory_arena_usage == 0
+        assert stats.num_segments == 0
+        assert stats.num_merging_segments == 0
+
+        for i in range(3):
+            writer.add_document(Document.from_dict({"id": i}, schema))
+        writer.delete_documents_by_term("id", 0)
+        stats = writer.stats()
+        assert stats.uncommitted_docs == 3
+        assert stats.uncommitted_operations == 4
+        assert 0 < stats.memory_arena_usage < stats.heap_size
+
+        writer.commit()
+        stats = writer.stats()
+        assert stats.uncommitted_docs == 0
+        assert stats.uncommitted_operations == 0
+        assert stats.memory_arena_usage == 0
+        assert stats.num_segments >= 1
+        writer.wait_merging_threads()
+
+        stats = index.writer(heap_size=15_000_000).stats()
+        assert stats.num_threads is None
+        assert stats.memory_budget_per_thread is None
+
     def test_create_readers(self):
         # not sure what is the point of this test.
         idx = Index(build_schema())
@@ -732,6 +1443,139 @@ class TestFromDiskClass(object):
         assert False
 
 
+class InMemoryStorage:
+    """A fake blob storage for the `directory` argument of `Index`."""
+
+    def __init__(self):
+        self.files = {}
+        self.num_reads = 0
+
+    def read_range(self, path, start, end):
+        self.num_reads += 1
+        try:
+            return self.files[path][start:end]
+        except KeyError:
+            raise FileNotFoundError(path)
+
+    def file_size(self, path):
+        try:
+            return len(self.files[path])
+        except KeyError:
+            raise FileNotFoundError(path)
+
+    def write(self, path, data):
+        self.files[path] = data
+
+    def atomic_write(self, path, data):
+        self.files[path] = data
+
+    def delete(self, path):
+        try:
+            del self.files[path]
+        except KeyError:
+            raise FileNotFoundError(path)
+
+
+class TestPythonDirectory:
+    def test_index_in_python_directory(self):
+        storage = InMemoryStorage()
+        schema = SchemaBuilder().add_text_field("title", stored=True).build()
+        index = Index(schema, directory=storage)
+        writer = index.writer()
+        with pytest.raises(tantivy.LockBusyError):
+            index.writer()
+        writer.add_document(Document(title="The Old Man and the Sea"))
+        writer.add_document(Document(title="Of Mice and Men"))
+        writer.commit()
+        writer.wait_merging_threads()
+        assert "meta.json" in storage.files
+
+        index = Index.open(directory=storage)
+        searcher = index.searcher()
+        assert searcher.num_docs == 2
+        query = index.parse_query("sea", ["title"])
+        _, address = searcher.search(query, 10).hits[0]
+        assert searcher.doc(address)["title"] == ["The Old Man and the Sea"]
+        assert index.verify().is_valid
+
+    def test_drop_writer_with_uncommitted_documents(self):
+        class RecordingStorage(InMemoryStorage):
+            def __init__(self):
+                super().__init__()
+                self.written = []
+
+            def write(self, path, data):
+                self.written.append(path)
+                super().write(path, data)
+
+        storage = RecordingStorage()
+        schema = SchemaBuilder().add_text_field("title", stored=True).build()
+        index = Index(schema, directory=storage)
+        writer = index.writer()
+        assert ".tantivy-writer.lock" in storage.files
+        writer.add_document(Document(title="The Old Man and the Sea"))
+        # The indexing threads need the GIL to write their segment files
+        # while the writer is dropped.
+        del writer
+        assert ".tantivy-writer.lock" not in storage.files
+        assert len(storage.written) == len(set(storage.written))
+
+        writer = index.writer()
+        writer.add_document(Document(title="Of Mice and Men"))
+        writer.commit()
+        writer.wait_merging_threads()
+        index.reload()
+        assert index.searcher().num_docs == 1
+
+    def test_read_cache(self):
+        storage = InMemoryStorage()
+        schema = SchemaBuilder().add_text_field("title", stored=True).build()
+        index = Index(schema, directory=storage, read_cache_size=10_000_000)
+        writer = index.writer()
+        writer.add_document(Document(title="The Old Man and the Sea"))
+        writer.commit()
+        writer.wait_merging_threads()
+
+        index.reload()
+        query = index.parse_query("sea", ["title"])
+        assert index.searcher().search(query, 10).count == 1
+        num_reads = storage.num_reads
+        assert index.searcher().search(query, 10).count == 1
+        assert storage.num_reads == num_reads
+
+    def test_preload_fills_read_cache(self):
+        storage = InMemoryStorage()
+        schema = SchemaBuilder().add_text_field("title", stored=True).build()
+        writer = Index(schema, directory=storage).writer()
+        writer.add_document(Document(title="The Old Man and the Sea"))
+        writer.commit()
+        writer.wait_merging_threads()
+
+        index = Index.open(
+            directory=storage,
+            read_cache_size=10_000_000,
+            preload=["postings", "positions", "terms", "store", "fieldnorms"],
+        )
+        num_reads = storage.num_reads
+        searcher = index.searcher()
+        query = index.parse_query("sea", ["title"])
+        _, address = searcher.search(query, 10).hits[0]
+        assert searcher.doc(address)["title"] == ["The Old Man and the Sea"]
+        assert storage.num_reads == num_reads
+
+        with pytest.raises(ValueError, match="read_cache_size"):
+            Index.open(directory=storage, read_cache_size=10, preload=["store"])
+        with pytest.raises(ValueError, match="read_cache_size"):
+            Index.open(directory=storage).preload(["store"])
+
+    def test_invalid_arguments(self, tmp_path):
+        schema = SchemaBuilder().add_text_field("title").build()
+        with pytest.raises(ValueError, match="Only one of"):
+            Index(schema, str(tmp_path), directory=InMemoryStorage())
+        with pytest.raises(ValueError, match="must be given"):
+            Index.open()
+
+
 class TestSearcher(object):
     def test_searcher_repr(self, ram_index, ram_index_numeric_fields):
         assert repr(ram_index.searcher()) == "Searcher(num_docs=3, num_segments=1)"
@@ -741,6 +1585,203 @@ class TestSearcher(object):
         )
 
 
+class TestMultiSearcher(object):
+    TITLES = [
+        ("The Old Man and the Sea", 1),
+        ("The Sea Wolf", 2),
+        ("Twenty Thousand Leagues Under the Sea", 3),
+        ("Of Mice and Men", 4),
+        ("The Man in the High Castle", 5),
+    ]
+
+    @staticmethod
+    def build_index(titles):
+        schema = (
+            SchemaBuilder()
+            .add_text_field("title", stored=True)
+            .add_integer_field("rank", stored=True, fast=True)
+            .build()
+        )
+        index = Index(schema)
+        writer = index.writer(15_000_000, 1)
+        for title, rank in titles:
+            writer.add_document(Document(title=title, rank=rank))
+        writer.commit()
+        writer.wait_merging_threads()
+        index.reload()
+        return index
+
+    def test_scores_match_single_index(self):
+        single = self.build_index(self.TITLES)
+        shards = [
+            self.build_index(self.TITLES[:2]),
+            self.build_index(self.TITLES[2:]),
+        ]
+        multi_searcher = tantivy.MultiSearcher(
+            [shard.searcher() for shard in shards]
+        )
+        assert repr(multi_searcher) == "MultiSearcher(num_docs=5, num_shards=2)"
+
+        query = single.parse_query("sea man", ["title"])
+        expected = single.searcher().search(query, 10)
+        result = multi_searcher.search(query, 10)
+        assert result.count == expected.count == 4
+        assert [score for score, _, _ in result.hits] == pytest.approx(
+            [score for score, _ in expected.hits]
+        )
+        titles = [
+            multi_searcher.doc(shard, address)["title"][0]
+            for _, shard, address in result.hits
+        ]
+        assert titles == [
+            single.searcher().doc(address)["title"][0]
+            for _, address in expected.hits
+        ]
+
+    def test_order_by_field_and_offset(self):
+        shards = [
+            self.build_index(self.TITLES[::2]),
+            self.build_index(self.TITLES[1::2]),
+        ]
+        multi_searcher = tantivy.MultiSearcher(
+            [shard.searcher() for shard in shards]
+        )
+        query = shards[0].parse_query("the", ["title"])
+
+        result = multi_searcher.search(
+            query, 2, order_by_field="rank", offset=1, order=tantivy.Order.Asc
+        )
+        assert result.count == 4
+        assert [(rank, shard) for rank, shard, _ in result.hits] == [(2, 1), (3, 0)]
+
+        result = multi_searcher.search(query, 10, count=False, order_by_field="rank")
+        assert result.count is None
+        assert [rank for rank, _, _ in result.hits] == [5, 3, 2, 1]
+
+    def test_invalid_arguments(self, ram_index, ram_index_numeric_fields):
+        with pytest.raises(ValueError, match="At least one searcher"):
+            tantivy.MultiSearcher([])
+        with pytest.raises(ValueError, match="same schema"):
+            tantivy.MultiSearcher(
+                [ram_index.searcher(), ram_index_numeric_fields.searcher()]
+            )
+        multi_searcher = tantivy.MultiSearcher([ram_index.searcher()])
+        with pytest.raises(ValueError, match="Invalid shard 1"):
+            multi_searcher.doc(1, tantivy.DocAddress(0, 0))
+
+
+class TestPercolator(object):
+    def test_match(self, ram_index):
+        index = ram_index
+        percolator = tantivy.Percolator(index.schema)
+        percolator.add("sea", index.parse_query("sea", ["title"]))
+        percolator.add("old-man", index.parse_query('"old man"', ["title", "body"]))
+        percolator.add("sea-not-wolf", index.parse_query("+sea -wolf", ["title"]))
+        percolator.add("mice-or-men", index.parse_query("mice men", ["title"]))
+        percolator.add("all", Query.all_query())
+        percolator.add("title-range", index.parse_query("title:[mia TO mid]"))
+        assert len(percolator) == 6
+        assert "sea" in percolator
+        assert repr(percolator) == "Percolator(num_queries=6)"
+
+        doc = Document(title="The Old Man and the Sea", body="A fish.")
+        assert percolator.match(doc) == ["all", "old-man", "sea", "sea-not-wolf"]
+        doc = Document(title="The Sea Wolf")
+        assert percolator.match(doc) == ["all", "sea"]
+        doc = Document(title="Of Mice and Men")
+        assert percolator.match(doc) == ["all", "mice-or-men", "title-range"]
+
+        assert percolator.remove("all")
+        assert not percolator.remove("all")
+        percolator.add("sea", index.parse_query("wolf", ["title"]))
+        assert percolator.match(Document(title="The Sea Wolf")) == ["sea"]
+
+    def test_match_boosted_queries(self, ram_index):
+        index = ram_index
+        percolator = tantivy.Percolator(index.schema)
+        percolator.add(
+            "sea",
+            index.parse_query("sea", ["title", "body"], field_boosts={"title": 2.0}),
+        )
+        percolator.add("wolf", index.parse_query("title:wolf^3 -title:sea"))
+        percolator.add(
+            "men",
+            Query.boost_query(
+                Query.const_score_query(
+                    Query.term_query(index.schema, "title", "men"), 1.0
+                ),
+                2.0,
+            ).or_should_match(Query.term_query(index.schema, "title", "mice")),
+        )
+
+        assert percolator.match(Document(title="The Sea Wolf")) == ["sea"]
+        assert percolator.match(Document(title="A Wolf")) == ["wolf"]
+        assert percolator.match(Document(body="By the sea")) == ["sea"]
+        assert percolator.match(Document(title="Of Mice and Men")) == ["men"]
+
+    def test_match_document_of_wrong_type(self):
+        schema = (
+            SchemaBuilder()
+            .add_text_field("title")
+            .add_unsigned_field("rank", fast=True)
+            .build()
+        )
+        percolator = tantivy.Percolator(schema)
+        percolator.add("all", Query.all_query())
+        with pytest.raises(ValueError, match="Expected U64 type for field rank"):
+            percolator.match(Document(title="a", rank=5))
+        with pytest.raises(ValueError, match="`year` is not defined"):
+            percolator.match(Document(title="a", year=5))
+        doc = Document.from_dict({"title": "a", "rank": 5}, schema)
+        assert percolator.match(doc) == ["all"]
+
+    def test_percolator_shared_between_threads(self, ram_index):
+        from concurrent.futures import ThreadPoolExecutor
+
+        index = ram_index
+        percolator = tantivy.Percolator(index.schema)
+        percolator.add("sea", index.parse_query("sea", ["title"]))
+        # Matching evaluates the queries without a term against every document.
+        for i in range(200):
+            percolator.add(f"all-{i}", Query.all_query())
+
+        def add_and_remove(i):
+            query = index.parse_query("wolf", ["title"])
+            for j in range(2000):
+                percolator.add(f"wolf-{i}-{j}", query)
+                percolator.remove(f"wolf-{i}-{j}")
+
+        def match(_):
+            for _ in range(200):
+                assert "sea" in percolator.match(Document(title="The Sea Wolf"))
+
+        with ThreadPoolExecutor(max_workers=8) as executor:
+            futures = [executor.submit(match, i) for i in range(4)]
+            futures += [executor.submit(add_and_remove, i) for i in range(4)]
+            for future in futures:
+                future.result()
+        assert len(percolator) == 201
+
+    def test_register_tokenizer(self):
+        schema = (
+            SchemaBuilder()
+            .add_text_field("title", tokenizer_name="raw_lower")
+            .build()
+        )
+        index = Index(schema)
+        analyzer = (
+            tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.raw())
+            .filter(tantivy.Filter.lowercase())
+            .build()
+        )
+        index.register_tokenizer("raw_lower", analyzer)
+        percolator = tantivy.Percolator(schema)
+        percolator.register_tokenizer("raw_lower", analyzer)
+        percolator.add("exact", index.parse_query('"the sea wolf"', ["title"]))
+        assert percolator.match(Document(title="The Sea Wolf")) == ["exact"]
+        assert percolator.match(Document(title="The Sea")) == []
+
+
 class TestDocument(object):
     def test_document(self):
         doc = tantivy.Document(name="Bill", reference=[1, 2])
@@ -1028,6 +2069,353 @@ def test_schema_pickle():
     assert orig == pickled
 
 
+def test_schema_fields():
+    schema = (
+        SchemaBuilder()
+        .add_text_field("title", stored=True, tokenizer_name="en_stem")
+        .add_text_field("tag", fast=True, index_option="basic")
+        .add_integer_field("id", stored=True, indexed=True, fast=True)
+        .add_date_field("date", fast=True)
+        .add_json_field("attrs", expand_dots_enabled=True)
+        .add_facet_field("category")
+        .build()
+    )
+    fields = schema.fields()
+    assert [f.name for f in fields] == ["title", "tag", "id", "date", "attrs", "category"]
+    assert [f.field_type for f in fields] == [
+        FieldType.Text,
+        FieldType.Text,
+        FieldType.Integer,
+        FieldType.Date,
+        FieldType.Json,
+        FieldType.Facet,
+    ]
+
+    title, tag, id_, date, attrs, _ = fields
+    assert (title.stored, title.indexed, title.fast) == (True, True, False)
+    assert title.tokenizer == "en_stem"
+    assert title.index_option == "position"
+    assert title.fieldnorms
+    assert title.expand_dots is None
+    assert title.precision is None
+
+    assert tag.fast and tag.fast_tokenizer == "default"
+    assert title.fast_tokenizer is None
+    assert tag.index_option == "basic"
+
+    assert (id_.stored, id_.indexed, id_.fast) == (True, True, True)
+    assert id_.tokenizer is None and id_.index_option is None
+    assert id_.coerce is False
+
+    assert date.precision == "seconds"
+    assert attrs.expand_dots is True
+    assert attrs.tokenizer == "default"
+
+    assert {f.field_type for f in fields} == {
+        FieldType.Text,
+        FieldType.Integer,
+        FieldType.Date,
+        FieldType.Json,
+        FieldType.Facet,
+    }
+    assert title.field_type != 0
+    assert schema.get_field_entry("title") == title
+    assert schema.get_field_entry("tag") != title
+    assert "name=\"title\"" in repr(title)
+    with pytest.raises(ValueError, match="not defined in the schema"):
+        schema.get_field_entry("missing")
+
+
+def test_schema_json_roundtrip():
+    schema = build_schema()
+    serialized = schema.to_json()
+    entries = json.loads(serialized)
+    assert [entry["name"] for entry in entries] == [
+        f.name for f in schema.fields()
+    ]
+    assert entries[0]["type"] == "text"
+
+    assert tantivy.Schema.from_json(serialized) == schema
+    with pytest.raises(ValueError):
+        tantivy.Schema.from_json('[{"name": "x", "type": "nope"}]')
+
+
+def test_schema_builder_text_options():
+    schema = (
+        SchemaBuilder()
+        .add_text_field("title", stored=True, fieldnorms=False)
+        .add_text_field("raw_json", stored=True, indexed=False)
+        .add_text_field(
+            "name", fast=True, tokenizer_name="en_stem", fast_tokenizer_name="default"
+        )
+        .add_text_field("code", fast=True, fast_tokenizer_name="raw")
+        .build()
+    )
+    title = schema.get_field_entry("title")
+    assert title.indexed and not title.fieldnorms
+
+    raw_json = schema.get_field_entry("raw_json")
+    assert raw_json.stored and not raw_json.indexed
+    assert raw_json.tokenizer is None
+
+    name = schema.get_field_entry("name")
+    assert (name.tokenizer, name.fast_tokenizer) == ("en_stem", "default")
+    code = schema.get_field_entry("code")
+    assert (code.tokenizer, code.fast_tokenizer) == ("default", None)
+
+    index = Index(schema)
+    writer = index.writer()
+    writer.add_document(Document(title="hello", raw_json='{"a": 1}'))
+    writer.commit()
+    index.reload()
+    searcher = index.searcher()
+    assert searcher.search(index.parse_query("hello", ["title"])).count == 1
+    with pytest.raises(ValueError, match="not set as indexed"):
+        index.parse_query("a", ["raw_json"])
+    _, address = searcher.search(index.parse_query("hello", ["title"])).hits[0]
+    assert searcher.doc(address)["raw_json"] == ['{"a": 1}']
+
+
+def test_schema_builder_numeric_options():
+    schema = (
+        SchemaBuilder()
+        .add_unsigned_field("id", stored=True, indexed=True, coerce=True)
+        .add_integer_field("rank", indexed=True, fieldnorms=True)
+        .add_float_field("score", stored=True)
+        .build()
+    )
+    id_entry = schema.get_field_entry("id")
+    assert id_entry.coerce and not id_entry.fieldnorms
+    rank = schema.get_field_entry("rank")
+    assert rank.fieldnorms and not rank.coerce
+
+    index = Index(schema)
+    writer = index.writer()
+    writer.add_json('{"id": "42", "rank": 1}')
+    with pytest.raises(ValueError):
+        writer.add_json('{"id": 1, "score": "1.5"}')
+    writer.commit()
+    index.reload()
+    query = Query.term_query(schema, "id", 42)
+    assert index.searcher().search(query).count == 1
+
+
+def test_schema_builder_date_precision():
+    schema = (
+        SchemaBuilder()
+        .add_date_field("default", fast=True)
+        .add_date_field("precise", fast=True, precision="microseconds")
+        .build()
+    )
+    assert schema.get_field_entry("default").precision == "seconds"
+    assert schema.get_field_entry("precise").precision == "microseconds"
+
+    with pytest.raises(ValueError, match="Invalid precision"):
+        SchemaBuilder().add_date_field("date", precision="hours")
+
+
+def test_schema_validate():
+    schema = (
+        SchemaBuilder()
+        .add_text_field("title", stored=True)
+        .add_unsigned_field("views", fast=True)
+        .add_ip_addr_field("ip")
+        .add_date_field("published")
+        .build()
+    )
+
+    assert schema.validate({"title": "Dracula", "views": 7}) == []
+    assert (
+        schema.validate(Document.from_dict({"views": 7}, schema)) == []
+    )
+
+    problems = schema.validate(
+        {
+            "title": ["Dracula", "Frankenstein"],
+            "views": -1,
+            "ip": ["10.0.0.1", "256.0.0.1", 42],
+            "published": "yesterday",
+            "author": "Bram Stoker",
+        },
+        single_valued=["title"],
+    )
+    assert [(p.field_name, p.kind) for p in problems] == [
+        ("title", "multiple_values"),
+        ("views", "invalid_value"),
+        ("ip", "invalid_value"),
+        ("ip", "wrong_type"),
+        ("published", "wrong_type"),
+        ("author", "unknown_field"),
+    ]
+    assert "256.0.0.1" in problems[2].message
+    assert problems[5].message == (
+        "Field `author` is not defined in the schema."
+    )
+
+    # Documents created without a schema convert integers to signed ones.
+    problems = schema.validate(Document(views=7))
+    assert [(p.field_name, p.kind) for p in problems] == [
+        ("views", "wrong_type")
+    ]
+
+    with pytest.raises(ValueError, match="Expected a Document or a dict"):
+        schema.validate(["title"])
+
+
+def test_schema_builder_infer():
+    samples = [
+        {"title": "Frankenstein", "rating": 4, "tags": ["novel"], "extra": None},
+        {
+            "title": "Dracula",
+            "rating": 4.5,
+            "published": datetime.datetime(1897, 5, 26),
+            "category": tantivy.Facet.from_string("/books/horror"),
+            "attrs": {"pages": 418},
+            "cover": b"\x89PNG",
+            "available": True,
+            "id": 2**63,
+        },
+    ]
+    schema = SchemaBuilder.infer(
+        samples,
+        overrides={
+            "title": {"tokenizer_name": "en_stem"},
+            "extra": {"type": FieldType.Text, "stored": False},
+            "views": {"type": FieldType.Unsigned, "fast": True},
+        },
+    ).build()
+
+    field_types = {
+        entry.name: entry.field_type for entry in schema.fields()
+    }
+    assert field_types == {
+        "title": FieldType.Text,
+        "rating": FieldType.Float,
+        "tags": FieldType.Text,
+        "extra": FieldType.Text,
+        "published": FieldType.Date,
+        "category": FieldType.Facet,
+        "attrs": FieldType.Json,
+        "cover": FieldType.Bytes,
+        "available": FieldType.Boolean,
+        "id": FieldType.Unsigned,
+        "views": FieldType.Unsigned,
+    }
+    assert [entry.name for entry in schema.fields()][:3] == [
+        "title",
+        "rating",
+        "tags",
+    ]
+    title = schema.get_field_entry("title")
+    assert title.stored and title.tokenizer == "en_stem"
+    assert not schema.get_field_entry("extra").stored
+    rating = schema.get_field_entry("rating")
+    assert rating.stored and rating.indexed and not rating.fast
+    assert schema.get_field_entry("views").fast
+
+    index = Index(schema)
+    writer = index.writer()
+    for sample in samples:
+        sample = {k: v for k, v in sample.items() if v is not None}
+        writer.add_document(Document.from_dict(sample, schema))
+    writer.commit()
+    index.reload()
+    assert index.searcher().num_docs == 2
+
+
+def test_schema_builder_infer_errors():
+    with pytest.raises(ValueError, match="incompatible types"):
+        SchemaBuilder.infer([{"value": 1}, {"value": "one"}])
+    with pytest.raises(ValueError, match="do not fit in a signed 64 bits"):
+        SchemaBuilder.infer([{"value": [1, 2**63]}])
+
+    schema = SchemaBuilder.infer([{"value": 1}, {"value": -2}]).build()
+    assert schema.get_field_entry("value").field_type == FieldType.Integer
+    schema = SchemaBuilder.infer([{"value": 2**63}, {"value": 0.5}]).build()
+    assert schema.get_field_entry("value").field_type == FieldType.Float
+
+    with pytest.raises(ValueError, match="all its sample values are None"):
+        SchemaBuilder.infer([{"value": None}])
+
+    with pytest.raises(ValueError, match="must be given in `overrides`"):
+        SchemaBuilder.infer([{"value": 1}], overrides={"other": {}})
+
+    schema = SchemaBuilder.infer(
+        [{"value": 1}, {"value": "one"}],
+        overrides={"value": {"type": FieldType.Text}},
+    ).build()
+    assert schema.get_field_entry("value").field_type == FieldType.Text
+
+
+def test_schema_builder_from_dataclass():
+    import dataclasses
+    import ipaddress
+    from typing import Annotated, ClassVar, Dict, List, Optional
+
+    @dataclasses.dataclass
+    class Book:
+        kind: ClassVar[str] = "book"
+        title: Annotated[str, {"tokenizer_name": "en_stem"}]
+        isbn: str = dataclasses.field(
+            metadata={"tantivy": {"tokenizer_name": "raw", "fast": True}}
+        )
+        pages: int = 0
+        rating: Optional[float] = None
+        available: bool = True
+        authors: List[str] = dataclasses.field(default_factory=list)
+        published: Optional[datetime.datetime] = None
+        category: Optional[tantivy.Facet] = None
+        cover: bytes = b""
+        attrs: Dict[str, Any] = dataclasses.field(default_factory=dict)
+        server: Optional[ipaddress.IPv4Address] = None
+        views: int = dataclasses.field(
+            default=0, metadata={"tantivy": {"type": FieldType.Unsigned}}
+        )
+
+    schema = SchemaBuilder.from_dataclass(Book).build()
+    assert [(entry.name, entry.field_type) for entry in schema.fields()] == [
+        ("title", FieldType.Text),
+        ("isbn", FieldType.Text),
+        ("pages", FieldType.Integer),
+        ("rating", FieldType.Float),
+        ("available", FieldType.Boolean),
+        ("authors", FieldType.Text),
+        ("published", FieldType.Date),
+        ("category", FieldType.Facet),
+        ("cover", FieldType.Bytes),
+        ("attrs", FieldType.Json),
+        ("server", FieldType.IpAddr),
+        ("views", FieldType.Unsigned),
+    ]
+    assert schema.get_field_entry("title").tokenizer == "en_stem"
+    isbn = schema.get_field_entry("isbn")
+    assert isbn.tokenizer == "raw" and isbn.fast and isbn.stored
+    assert schema.get_field_entry("pages").indexed
+
+
+def test_schema_builder_from_typed_dict():
+    from typing import Annotated, Optional, TypedDict
+
+    class Movie(TypedDict, total=False):
+        title: str
+        year: Annotated[int, {"fast": True}]
+        summary: Optional[str]
+
+    schema = SchemaBuilder.from_dataclass(Movie).build()
+    assert [(entry.name, entry.field_type) for entry in schema.fields()] == [
+        ("title", FieldType.Text),
+        ("year", FieldType.Integer),
+        ("summary", FieldType.Text),
+    ]
+    assert schema.get_field_entry("year").fast
+
+    class Unsupported(TypedDict):
+        value: complex
+
+    with pytest.raises(ValueError, match="Cannot map the annotation"):
+        SchemaBuilder.from_dataclass(Unsupported)
+
+
 def test_facet_pickle():
     orig = tantivy.Facet.from_string("/europe/france")
     pickled = pickle.loads(pickle.dumps(orig))
@@ -2178,6 +3566,199 @@ class TestQuery(object):
         result = searcher.search(query, 10)
         assert len(result.hits) == 0
 
+    def test_query_to_dict(self, ram_index, ram_index_numeric_fields):
+        index = ram_index
+        schema = index.schema
+        sea = Query.term_query(schema, "title", "sea")
+        queries = [
+            sea,
+            Query.term_set_query(schema, "title", ["sea", "old"]),
+            Query.all_query(),
+            Query.fuzzy_term_query(schema, "title", "see"),
+            Query.phrase_query(schema, "title", [(1, "man"), (0, "old")], slop=1),
+            Query.regex_phrase_query(schema, "title", ["o.d", "ma[nm]"]),
+            Query.phrase_prefix_query(schema, "title", ["old", "ma"]),
+            Query.boolean_query(
+                [
+                    (Occur.Should, sea),
+                    (Occur.MustNot, Query.term_query(schema, "body", "fish")),
+                ]
+            ),
+            Query.disjunction_max_query(
+                [sea, Query.term_query(schema, "body", "sea")], tie_breaker=0.5
+            ),
+            Query.boost_query(sea, 2.0),
+            Query.const_score_query(sea, 3.0),
+            Query.regex_query(schema, "body", "fish"),
+            sea.or_should_match(Query.term_query(schema, "title", "old")),
+            Query.more_like_this_document_fields_query(
+                schema,
+                {"body": "the old man and the sea"},
+                min_doc_frequency=1,
+                min_term_frequency=1,
+            ),
+        ]
+        searcher = index.searcher()
+        for query in queries:
+            query_dict = json.loads(json.dumps(query.to_dict()))
+            for copied in [Query.from_dict(schema, query_dict), pickle.loads(pickle.dumps(query))]:
+                assert repr(copied) == repr(query)
+                assert searcher.search(copied, 10).hits == searcher.search(query, 10).hits
+
+        assert sea.to_dict() == {
+            "type": "term",
+            "term": {"field": "title", "type": "str", "value": "sea"},
+            "index_option": "position",
+        }
+        # The fields are found by name in a schema with another field order.
+        schema = (
+            SchemaBuilder()
+            .add_text_field("body")
+            .add_text_field("title", stored=True)
+            .build()
+        )
+        assert repr(Query.from_dict(schema, sea.to_dict())) == repr(
+            Query.term_query(schema, "title", "sea")
+        )
+
+        index = ram_index_numeric_fields
+        query = Query.range_query(
+            index.schema, "rating", FieldType.Float, 3.5, None, include_lower=False
+        )
+        copied = pickle.loads(pickle.dumps(query))
+        assert index.searcher().search(copied, 10).hits == index.searcher().search(query, 10).hits
+
+    def test_query_to_dict_errors(self, ram_index):
+        index = ram_index
+        parsed = index.parse_query("sea")
+        with pytest.raises(ValueError, match="cannot be serialized"):
+            parsed.to_dict()
+        with pytest.raises(ValueError, match="cannot be serialized"):
+            pickle.dumps(parsed)
+        with pytest.raises(ValueError, match="cannot be serialized"):
+            Query.boost_query(parsed, 2.0).to_dict()
+        doc_address = index.searcher().search(Query.all_query(), 1).hits[0][1]
+        with pytest.raises(ValueError, match="cannot be serialized"):
+            Query.more_like_this_query(doc_address).to_dict()
+        with pytest.raises(ValueError, match="no field `author`"):
+            Query.from_dict(
+                index.schema,
+                {"type": "exists", "field": "author", "json_subpaths": False},
+            )
+
+        with pytest.raises(ValueError, match="at least two terms"):
+            Query.from_dict(
+                index.schema,
+                {
+                    "type": "phrase",
+                    "terms": [[0, {"field": "title", "type": "str", "value": "sea"}]],
+                    "slop": 0,
+                }
+            )
+        with pytest.raises(ValueError, match="at least one bound"):
+            Query.from_dict(
+                index.schema,
+                {
+                    "type": "range",
+                    "lower_bound": "unbounded",
+                    "upper_bound": "unbounded",
+                    "use_inverted_index": False,
+                }
+            )
+        with pytest.raises(ValueError, match="unknown variant"):
+            Query.from_dict(index.schema, {"type": "unknown"})
+
+        with pytest.raises(ValueError, match="no field `author`"):
+            Query.from_dict(
+                index.schema,
+                {
+                    "type": "term",
+                    "term": {"field": "author", "type": "str", "value": "sea"},
+                    "index_option": "position",
+                },
+            )
+        with pytest.raises(ValueError, match="holds Str values, not U64"):
+            Query.from_dict(
+                index.schema,
+                {
+                    "type": "term",
+                    "term": {"field": "title", "type": "u64", "value": 1},
+                    "index_option": "position",
+                },
+            )
+
+    def test_query_introspection(self, ram_index):
+        index = ram_index
+        query = index.parse_query(
+            '+title:sea^2 +(body:"old man" title:whale) -body:fish', ["title"]
+        )
+        assert query.kind == "boolean"
+        assert query.boost == 1.0
+
+        clauses = query.clauses()
+        assert [(occur, clause.kind) for occur, clause in clauses] == [
+            (Occur.Must, "boost"),
+            (Occur.Must, "boolean"),
+            (Occur.MustNot, "term"),
+        ]
+        assert clauses[0][1].boost == 2.0
+        assert clauses[0][1].terms() == [("title", "sea")]
+        assert clauses[1][1].terms() == [
+            ("body", "old"),
+            ("body", "man"),
+            ("title", "whale"),
+        ]
+        assert Query.term_query(index.schema, "title", "sea").clauses() == []
+
+        assert query.terms() == [
+            ("title", "sea"),
+            ("body", "old"),
+            ("body", "man"),
+            ("title", "whale"),
+            ("body", "fish"),
+        ]
+        # With a searcher, the terms that do not occur in the index are
+        # dropped.
+        assert ("title", "whale") not in query.terms(index.searcher())
+
+        query = Query.from_dict(
+            index.schema, Query.term_query(index.schema, "title", "sea").to_dict()
+        )
+        assert query.terms() == [("title", "sea")]
+        assert Query.all_query().terms() == []
+
+        assert Occur.Must == Occur.Must
+        assert Occur.Must != 0
+        assert len({Occur.Must, Occur.Must, Occur.Should}) == 2
+
+    def test_query_introspection_boosts(self, ram_index):
+        index = ram_index
+        schema = index.schema
+        query = index.parse_query(
+            "+title:sea^3 +body:man title:whale", field_boosts={"title": 2.0}
+        )
+        clauses = query.clauses()
+        # The boosts of the fields are not exposed by tantivy.
+        assert [(clause.kind, clause.boost) for _, clause in clauses] == [
+            ("boost", 3.0),
+            ("term", 1.0),
+            ("boost", None),
+        ]
+        query, errors = index.parse_query_lenient("title:sea^2 nofield:old^4")
+        assert query.boost == 1.0
+        assert [clause.boost for _, clause in query.clauses()] == [2.0, 4.0]
+        assert index.parse_query("title:sea^0.5").boost == 0.5
+
+        sea = Query.term_query(schema, "title", "sea")
+        query = Query.boost_query(sea, 2.5)
+        assert query.boost == 2.5
+        assert Query.from_dict(schema, query.to_dict()).boost == 2.5
+        assert Query.const_score_query(query, 1.0).boost == 1.0
+        query = Query.boolean_query([(Occur.Must, query)]).and_must_match(
+            Query.boost_query(index.parse_query("old"), 4.0)
+        )
+        assert [clause.boost for _, clause in query.clauses()] == [2.5, 4.0]
+
 
 class TestTokenizers:
     def test_build_and_register_simple_tokenizer(self):
@@ -2274,6 +3855,297 @@ class TestTokenizers:
         doc_text = "that is, like, such a weird way to, like, test"
         assert ["weird", "way", "test"] == analyzer.analyze(doc_text)
 
+    def test_build_tokenizer_w_split_compound_filter(self):
+        analyzer = (
+            tantivy.TextAnalyzerBuilder(tokenizer=tantivy.Tokenizer.simple())
+            .filter(tantivy.Filter.split_compound(["dampf", "schiff", "fahrt"]))
+            .build()
+        )
+        assert ["dampf", "schiff", "fahrt", "brot"] == analyzer.analyze(
+            "dampfschifffahrt brot"
+        )
+
+    def test_build_invalid_filters_raise(self):
+        with pytest.raises(ValueError, match="Invalid ngram tokenizer"):
+            tantivy.TextAnalyzerBuilder(
+                tokenizer=tantivy.Tokenizer.ngram(min_gram=3, max_gram=2)
+            )
+        with pytest.raises(ValueError, match="min_gram"):
+            tantivy.Filter.edge_ngram(min_gram=3, max_gram=2)
+        with pytest.raises(ValueError, match="min_gram"):
+            tantivy.Filter.edge_ngram(min_gram=0, max_gram=2)
+        with pytest.raises(ValueError, match="Unsupported language"):
+            tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.simple()).filter(
+                tantivy.Filter.stopword("klingon")
+            )
+        with pytest.raises(ValueError, match="synonyms must be"):
+            tantivy.Filter.synonym("car")
+
+    def test_build_tokenizer_w_edge_ngram_filter(self):
+        analyzer = (
+            tantivy.TextAnalyzerBuilder(tokenizer=tantivy.Tokenizer.simple())
+            .filter(tantivy.Filter.lowercase())
+            .filter(tantivy.Filter.edge_ngram(min_gram=2, max_gram=4))
+            .build()
+        )
+        tokens = analyzer.analyze_tokens("Tantivy é rs")
+        assert [t.text for t in tokens] == ["ta", "tan", "tant", "rs"]
+        assert [t.position for t in tokens] == [0, 0, 0, 2]
+        assert [(t.offset_from, t.offset_to) for t in tokens][:3] == [(0, 7)] * 3
+
+        analyzer = (
+            tantivy.TextAnalyzerBuilder(tokenizer=tantivy.Tokenizer.simple())
+            .filter(
+                tantivy.Filter.edge_ngram(min_gram=2, max_gram=3, preserve_original=True)
+            )
+            .build()
+        )
+        assert analyzer.analyze("héllo a ab") == ["hé", "hél", "héllo", "a", "ab"]
+
+    def test_edge_ngram_autocomplete(self):
+        schema = (
+            tantivy.SchemaBuilder()
+            .add_text_field("title", stored=True, tokenizer_name="autocomplete")
+            .build()
+        )
+        index = tantivy.Index(schema)
+        index.register_tokenizer(
+            "autocomplete",
+            tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.simple())
+            .filter(tantivy.Filter.lowercase())
+            .filter(tantivy.Filter.edge_ngram(min_gram=1, max_gram=10))
+            .build(),
+        )
+        writer = index.writer()
+        writer.add_document(Document(title="Frankenstein"))
+        writer.add_document(Document(title="Of Mice and Men"))
+        writer.commit()
+        index.reload()
+
+        query = Query.term_query(schema, "title", "fran")
+        assert index.searcher().search(query).count == 1
+        query = Query.term_query(schema, "title", "m")
+        assert index.searcher().search(query).count == 1
+
+    def test_build_tokenizer_w_synonym_filter(self):
+        analyzer = (
+            tantivy.TextAnalyzerBuilder(tokenizer=tantivy.Tokenizer.simple())
+            .filter(tantivy.Filter.lowercase())
+            .filter(
+                tantivy.Filter.synonym(
+                    {"new york": ["nyc"], "tv": ["television", "telly box"]}
+                )
+            )
+            .build()
+        )
+        tokens = analyzer.analyze_tokens("New York TV show")
+        assert [(t.text, t.position, t.position_length) for t in tokens] == [
+            ("new", 0, 1),
+            ("nyc", 0, 2),
+            ("york", 1, 1),
+            ("tv", 2, 1),
+            ("television", 2, 1),
+            ("telly", 2, 1),
+            ("box", 2, 1),
+            ("show", 3, 1),
+        ]
+        nyc = tokens[1]
+        assert (nyc.offset_from, nyc.offset_to) == (0, 8)
+
+        groups = (
+            tantivy.TextAnalyzerBuilder(tokenizer=tantivy.Tokenizer.simple())
+            .filter(tantivy.Filter.synonym([["car", "automobile"]]))
+            .build()
+        )
+        assert groups.analyze("car") == ["car", "automobile"]
+        assert groups.analyze("automobile") == ["automobile", "car"]
+
+    def test_synonym_search(self):
+        schema = (
+            tantivy.SchemaBuilder()
+            .add_text_field("body", stored=True, tokenizer_name="synonyms")
+            .build()
+        )
+        index = tantivy.Index(schema)
+        index.register_tokenizer(
+            "synonyms",
+            tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.simple())
+            .filter(tantivy.Filter.lowercase())
+            .filter(tantivy.Filter.synonym({"nyc": ["new york"]}))
+            .build(),
+        )
+        writer = index.writer()
+        writer.add_document(Document(body="Flights to NYC today"))
+        writer.commit()
+        index.reload()
+
+        for query in ["new", "york", "nyc", '"to nyc today"', '"to york"']:
+            parsed = index.parse_query(query, ["body"])
+            assert index.searcher().search(parsed).count == 1, query
+
+    def test_text_analyzer_json_roundtrip(self):
+        analyzer = (
+            tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.regex(r"[a-z]+"))
+            .filter(tantivy.Filter.lowercase())
+            .filter(tantivy.Filter.custom_stopword(["the"]))
+            .filter(tantivy.Filter.synonym({"sea": ["ocean"]}))
+            .build()
+        )
+        definition = json.loads(analyzer.to_json())
+        assert definition["tokenizer"] == {"type": "regex", "pattern": "[a-z]+"}
+        assert [f["type"] for f in definition["filters"]] == [
+            "lowercase",
+            "custom_stopword",
+            "synonym",
+        ]
+
+        restored = tantivy.TextAnalyzer.from_json(analyzer.to_json())
+        assert restored.analyze("the sea") == ["sea", "ocean"]
+        assert restored.to_json() == analyzer.to_json()
+
+        minimal = tantivy.TextAnalyzer.from_json('{"tokenizer": {"type": "raw"}}')
+        assert minimal.analyze("Hello World") == ["Hello World"]
+
+        with pytest.raises(ValueError):
+            tantivy.TextAnalyzer.from_json('{"tokenizer": {"type": "nope"}}')
+        with pytest.raises(ValueError, match="Unsupported language"):
+            tantivy.TextAnalyzer.from_json(
+                '{"tokenizer": {"type": "simple"},'
+                ' "filters": [{"type": "stemmer", "language": "klingon"}]}'
+            )
+
+    def test_registered_analyzers_persist_with_index(self, tmp_path):
+        schema = (
+            tantivy.SchemaBuilder()
+            .add_text_field("body", stored=True, tokenizer_name="custom")
+            .add_text_field("tag", fast=True, tokenizer_name="raw")
+            .build()
+        )
+        index = tantivy.Index(schema, str(tmp_path))
+        custom = (
+            tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.whitespace())
+            .filter(tantivy.Filter.lowercase())
+            .build()
+        )
+        index.register_tokenizer("custom", custom)
+        index.register_fast_field_tokenizer("custom_fast", custom)
+        index.register_tokenizer(
+            "ephemeral",
+            tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.raw()).build(),
+            persist=False,
+        )
+        writer = index.writer()
+        writer.add_document(Document(body="Hello-World Foo"))
+        writer.commit()
+        writer.wait_merging_threads()
+
+        reopened = Index.open(str(tmp_path))
+        analyzer = reopened.tokenizer_for_field("body")
+        assert analyzer.analyze("Hello-World Foo") == ["hello-world", "foo"]
+        reopened.reload()
+        query = reopened.parse_query("hello-world", ["body"])
+        assert reopened.searcher().search(query).count == 1
+
+        # Reusing the directory through the constructor also restores them.
+        reused = tantivy.Index(schema, str(tmp_path))
+        assert reused.tokenizer_for_field("body").analyze("A B") == ["a", "b"]
+
+        persisted = json.loads((tmp_path / ".tantivy-py-analyzers.json").read_text())
+        assert list(persisted["tokenizers"]) == ["custom"]
+        assert list(persisted["fast_field_tokenizers"]) == ["custom_fast"]
+
+    def test_register_unserializable_analyzer(self, tmp_path):
+        schema = tantivy.SchemaBuilder().add_text_field("body").build()
+        index = tantivy.Index(schema, str(tmp_path))
+        analyzer = index.tokenizer_for_field("body")
+        with pytest.raises(ValueError, match="cannot be serialized"):
+            analyzer.to_json()
+        with pytest.raises(ValueError, match="cannot be persisted"):
+            index.register_tokenizer("copy", analyzer, persist=True)
+        # Without an explicit request, it is registered in memory only.
+        index.register_tokenizer("copy", analyzer)
+        assert not (tmp_path / ".tantivy-py-analyzers.json").exists()
+
+    def test_concurrent_analyzer_registration(self, tmp_path):
+        from concurrent.futures import ThreadPoolExecutor
+
+        schema = tantivy.SchemaBuilder().add_text_field("body").build()
+        indexes = [tantivy.Index(schema, str(tmp_path)), Index.open(str(tmp_path))]
+        analyzer = tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.raw()).build()
+
+        def register(index, prefix):
+            for i in range(20):
+                index.register_tokenizer(f"{prefix}{i}", analyzer)
+
+        with ThreadPoolExecutor(max_workers=2) as executor:
+            list(executor.map(register, indexes, "ab"))
+        sidecar = tmp_path / ".tantivy-py-analyzers.json"
+        assert len(json.loads(sidecar.read_text())["tokenizers"]) == 40
+
+    def test_damaged_analyzers_sidecar(self, tmp_path):
+        schema = tantivy.SchemaBuilder().add_text_field("body").build()
+        index = tantivy.Index(schema, str(tmp_path))
+        index.register_tokenizer(
+            "custom",
+            analyzer=tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.raw()).build(),
+        )
+        sidecar = tmp_path / ".tantivy-py-analyzers.json"
+        assert list(json.loads(sidecar.read_text())["tokenizers"]) == ["custom"]
+
+        sidecar.write_text("not json")
+        with pytest.warns(RuntimeWarning, match="Skipping the analyzers"):
+            reopened = Index.open(str(tmp_path))
+        assert reopened.searcher().num_docs == 0
+
+    def test_analyze_tokens(self):
+        analyzer = (
+            tantivy.TextAnalyzerBuilder(tokenizer=tantivy.Tokenizer.simple())
+            .filter(tantivy.Filter.lowercase())
+            .build()
+        )
+        tokens = analyzer.analyze_tokens("Hello, Wörld again")
+        assert [t.text for t in tokens] == ["hello", "wörld", "again"]
+        assert [(t.offset_from, t.offset_to) for t in tokens] == [
+            (0, 5),
+            (7, 13),
+            (14, 19),
+        ]
+        assert [t.position for t in tokens] == [0, 1, 2]
+        assert all(t.position_length == 1 for t in tokens)
+        assert tokens == analyzer.analyze_tokens("Hello, Wörld again")
+
+    def test_tokenizer_for_field(self):
+        schema = (
+            tantivy.SchemaBuilder()
+            .add_text_field("title")
+            .add_text_field("tag", tokenizer_name="raw")
+            .add_text_field("body", tokenizer_name="en_custom")
+            .add_integer_field("id", indexed=True)
+            .build()
+        )
+        index = tantivy.Index(schema)
+        index.register_tokenizer(
+            "en_custom",
+            tantivy.TextAnalyzerBuilder(tantivy.Tokenizer.whitespace())
+            .filter(tantivy.Filter.stemmer("english"))
+            .build(),
+        )
+
+        title = index.tokenizer_for_field("title")
+        assert title.analyze("The Old-Man") == ["the", "old", "man"]
+        tag = index.tokenizer_for_field("tag")
+        assert tag.analyze("The Old-Man") == ["The Old-Man"]
+        body = index.tokenizer_for_field("body")
+        assert [t.text for t in body.analyze_tokens("fishing boats")] == [
+            "fish",
+            "boat",
+        ]
+
+        with pytest.raises(ValueError):
+            index.tokenizer_for_field("id")
+        with pytest.raises(ValueError):
+            index.tokenizer_for_field("missing")
+
     def test_delete_documents_by_query(self):
         schema_builder = SchemaBuilder()
         schema_builder.add_text_field("id", fast=True)